@@ -0,0 +1,548 @@
+use crate::commands::doctor::miner_address;
+use crate::config::activation::ActivationHeights;
+use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
+use crate::error::{Result, ZecKitError};
+use crate::indexer::proto::{CompactBlock, CompactTx, LightdInfo, RawTransaction, TreeState};
+use crate::indexer::{IndexerClient, INDEXER_URL, SIDE_LWD_PORT, SIDE_LWD_URL};
+use crate::utils;
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+
+const INDEXERS: [&str; 2] = ["zaino", "lightwalletd"];
+
+/// GetTransaction is called for at most this many transactions.
+const MAX_TRANSACTIONS: usize = 20;
+
+/// Differences printed per check; the JSON report has all of them.
+const MAX_PRINTED_DIFFERENCES: usize = 5;
+
+pub struct CompareOptions {
+    /// How many blocks below the common tip to compare.
+    pub blocks: u64,
+    /// Transparent address for GetTaddressTxids (default: the miner address).
+    pub address: Option<String>,
+    /// Where to write the JSON report.
+    pub output: Option<PathBuf>,
+    /// Leave both indexers running afterwards.
+    pub keep: bool,
+}
+
+/// One field where the two indexers answered differently.
+#[derive(Debug, PartialEq)]
+struct Difference {
+    path: String,
+    zaino: Value,
+    lightwalletd: Value,
+}
+
+enum Outcome {
+    Match,
+    Mismatch(Vec<Difference>),
+    /// Both indexers failed the call, so there is nothing to compare.
+    Error(String),
+}
+
+/// The result of calling one method with one request on both indexers.
+struct Check {
+    method: &'static str,
+    request: String,
+    outcome: Outcome,
+}
+
+/// Run Zaino and lightwalletd against the same Zebra and diff their answers
+/// to the same `CompactTxStreamer` calls.
+pub async fn execute(options: CompareOptions) -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  ZecKit - Compare Backends".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    if options.blocks == 0 {
+        return Err(ZecKitError::Config("--blocks must be at least 1".into()));
+    }
+
+    let project_dir = utils::project_dir()?;
+    let base = ActivationHeights::load(&project_dir)?
+        .env()
+        .into_iter()
+        .fold(DockerCompose::new()?, |compose, (key, value)| compose.with_env(key, &value));
+
+    let running = base.running_services()?;
+    if !running.iter().any(|s| s == "zebra") {
+        return Err(ZecKitError::ServiceNotReady(
+            "Zebra is not running; start the devnet with `zeckit up` first".into(),
+        ));
+    }
+
+    // The dual profile already serves both indexers on these ports
+    if running.iter().any(|s| s == "lightwalletd-dual") {
+        println!("Using the dual profile's Zaino on {} and lightwalletd on {}", INDEXER_URL, SIDE_LWD_URL);
+        return compare_indexers(&project_dir, &options).await;
+    }
+
+    // Zaino keeps the usual port; lightwalletd moves aside
+    println!("Starting Zaino on {} and lightwalletd on {}...", INDEXER_URL, SIDE_LWD_URL);
+    let compare = base.clone().with_env("ZECKIT_LWD_PORT", SIDE_LWD_PORT);
+    // A running lightwalletd holds 9067, so move it before Zaino binds there
+    if running.iter().any(|s| s == "lightwalletd") {
+        compare.recreate("lightwalletd")?;
+    }
+    compare.up(&INDEXERS)?;
+
+    let result = compare_indexers(&project_dir, &options).await;
+
+    if options.keep {
+        println!("Both indexers left running (lightwalletd on {})", SIDE_LWD_URL);
+    } else {
+        restore(&base, &running);
+    }
+
+    result
+}
+
+async fn compare_indexers(project_dir: &std::path::Path, options: &CompareOptions) -> Result<()> {
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(ProgressStyle::default_spinner().template("{spinner:.green} {msg}").unwrap());
+
+    let checker = HealthChecker::new();
+    checker.wait_for_indexer("Zaino", INDEXER_URL, &pb).await?;
+    checker.wait_for_indexer("Lightwalletd", SIDE_LWD_URL, &pb).await?;
+    pb.finish_and_clear();
+    println!("✓ Both indexers caught up with Zebra");
+    println!();
+
+    let zaino = IndexerClient::connect(INDEXER_URL).await?;
+    let lwd = IndexerClient::connect(SIDE_LWD_URL).await?;
+    let pair = Pair { zaino, lwd };
+
+    // The miner keeps going, so compare at the lower of the two tips
+    let (zaino_tip, lwd_tip) = pair
+        .call(|mut c| async move { Ok(json!(c.get_latest_block().await?.height)) })
+        .await;
+    let tip = zaino_tip?.as_u64().unwrap_or(0).min(lwd_tip?.as_u64().unwrap_or(0));
+    let start = tip.saturating_sub(options.blocks - 1).max(1);
+
+    let address = match &options.address {
+        Some(address) => Some(address.clone()),
+        None => fs::read_to_string(project_dir.join("docker/configs/zebra.toml"))
+            .ok()
+            .and_then(|c| miner_address(&c)),
+    };
+
+    println!("Comparing blocks {}..={}", start, tip);
+    println!();
+
+    let mut checks = Vec::new();
+
+    let (a, b) = pair
+        .call(|mut c| async move { Ok(lightd_info_json(&c.get_lightd_info().await?)) })
+        .await;
+    checks.push(Check::new("GetLightdInfo", String::new(), a, b));
+
+    let (a, b) = pair
+        .call(|mut c| async move {
+            Ok(Value::Array(c.get_block_range(start, tip).await?.iter().map(compact_block_json).collect()))
+        })
+        .await;
+    let txids = block_txids(&a, &b);
+    checks.push(Check::new("GetBlockRange", format!("{}..={}", start, tip), a, b));
+
+    for height in start..=tip {
+        let (a, b) = pair
+            .call(|mut c| async move { Ok(tree_state_json(&c.get_tree_state(height).await?)) })
+            .await;
+        checks.push(Check::new("GetTreeState", format!("height {}", height), a, b));
+    }
+
+    for hash in txids.into_iter().take(MAX_TRANSACTIONS) {
+        let request = hash_hex(&hash);
+        let (a, b) = pair
+            .call(|mut c| {
+                let hash = hash.clone();
+                async move { Ok(raw_transaction_json(&c.get_transaction(&hash).await?)) }
+            })
+            .await;
+        checks.push(Check::new("GetTransaction", request, a, b));
+    }
+
+    match address {
+        Some(address) => {
+            let request = format!("{} 1..={}", address, tip);
+            let (a, b) = pair
+                .call(|mut c| {
+                    let address = address.clone();
+                    async move {
+                        let mut txs: Vec<Value> = c
+                            .get_taddress_txids(&address, 1, tip)
+                            .await?
+                            .iter()
+                            .map(raw_transaction_json)
+                            .collect();
+                        txs.sort_by_key(|tx| tx.to_string());
+                        Ok(Value::Array(txs))
+                    }
+                })
+                .await;
+            checks.push(Check::new("GetTaddressTxids", request, a, b));
+        }
+        None => println!("{}", "Skipping GetTaddressTxids: no --address and no miner address in zebra.toml".yellow()),
+    }
+
+    // Ordering of mempool entries is unspecified
+    let (a, b) = pair
+        .call(|mut c| async move {
+            let mut txs: Vec<Value> = c.get_mempool_tx().await?.iter().map(compact_tx_json).collect();
+            txs.sort_by_key(|tx| tx["hash"].to_string());
+            Ok(Value::Array(txs))
+        })
+        .await;
+    checks.push(Check::new("GetMempoolTx", String::new(), a, b));
+
+    print_report(&checks);
+
+    if let Some(output) = &options.output {
+        if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(output, serde_json::to_string_pretty(&report_json(start, tip, &checks))?)?;
+        println!("  Report: {}", output.display());
+        println!();
+    }
+
+    let failed = checks.iter().filter(|c| !matches!(c.outcome, Outcome::Match)).count();
+    if failed > 0 {
+        return Err(ZecKitError::HealthCheck(format!(
+            "{} of {} checks differ between Zaino and lightwalletd",
+            failed,
+            checks.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Put the indexers back the way they were: stop the ones that weren't
+/// running, and move lightwalletd back to its usual port if it ran alone.
+fn restore(compose: &DockerCompose, running: &[String]) {
+    let was_running = |service: &str| running.iter().any(|s| s == service);
+
+    for service in INDEXERS {
+        let result = if !was_running(service) {
+            compose.remove(service)
+        } else if service == "lightwalletd" && !was_running("zaino") {
+            compose.recreate(service)
+        } else {
+            Ok(())
+        };
+
+        if let Err(e) = result {
+            println!("{}", format!("Warning: could not restore {}: {}", service, e).yellow());
+        }
+    }
+}
+
+struct Pair {
+    zaino: IndexerClient,
+    lwd: IndexerClient,
+}
+
+impl Pair {
+    /// Make the same call on both indexers at once, so the chain and mempool
+    /// have as little time as possible to move in between.
+    async fn call<F, Fut>(&self, f: F) -> (Result<Value>, Result<Value>)
+    where
+        F: Fn(IndexerClient) -> Fut,
+        Fut: Future<Output = Result<Value>>,
+    {
+        tokio::join!(f(self.zaino.clone()), f(self.lwd.clone()))
+    }
+}
+
+impl Check {
+    fn new(method: &'static str, request: String, zaino: Result<Value>, lwd: Result<Value>) -> Self {
+        let outcome = match (zaino, lwd) {
+            (Err(a), Err(b)) => Outcome::Error(format!("zaino: {}; lightwalletd: {}", a, b)),
+            (a, b) => {
+                let mut differences = Vec::new();
+                diff_values("", &or_error(a), &or_error(b), &mut differences);
+                if differences.is_empty() {
+                    Outcome::Match
+                } else {
+                    Outcome::Mismatch(differences)
+                }
+            }
+        };
+
+        Self { method, request, outcome }
+    }
+
+    fn label(&self) -> String {
+        if self.request.is_empty() {
+            self.method.to_string()
+        } else {
+            format!("{} ({})", self.method, self.request)
+        }
+    }
+}
+
+/// A failed call on one side only is a difference like any other.
+fn or_error(result: Result<Value>) -> Value {
+    result.unwrap_or_else(|e| json!({ "error": e.to_string() }))
+}
+
+/// Record every leaf where `a` and `b` differ, with a dotted path to it.
+fn diff_values(path: &str, a: &Value, b: &Value, out: &mut Vec<Difference>) {
+    match (a, b) {
+        (Value::Object(x), Value::Object(y)) => {
+            let keys: BTreeSet<&String> = x.keys().chain(y.keys()).collect();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff_values(
+                    &child,
+                    x.get(key).unwrap_or(&Value::Null),
+                    y.get(key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(x), Value::Array(y)) => {
+            if x.len() != y.len() {
+                out.push(Difference {
+                    path: format!("{}.length", path),
+                    zaino: json!(x.len()),
+                    lightwalletd: json!(y.len()),
+                });
+            }
+            for i in 0..x.len().min(y.len()) {
+                diff_values(&format!("{}[{}]", path, i), &x[i], &y[i], out);
+            }
+        }
+        _ if a != b => out.push(Difference {
+            path: path.to_string(),
+            zaino: a.clone(),
+            lightwalletd: b.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn print_report(checks: &[Check]) {
+    for check in checks {
+        match &check.outcome {
+            Outcome::Match => println!("  {} {}", "✓".green(), check.label()),
+            Outcome::Error(e) => println!("  {} {}: {}", "✗".red(), check.label(), e),
+            Outcome::Mismatch(differences) => {
+                println!("  {} {}: {} difference(s)", "✗".red(), check.label(), differences.len());
+                for d in differences.iter().take(MAX_PRINTED_DIFFERENCES) {
+                    let path = if d.path.is_empty() { "(response)" } else { d.path.as_str() };
+                    println!("        {}", path.dimmed());
+                    println!("          zaino:        {}", d.zaino);
+                    println!("          lightwalletd: {}", d.lightwalletd);
+                }
+                if differences.len() > MAX_PRINTED_DIFFERENCES {
+                    println!("        ... and {} more", differences.len() - MAX_PRINTED_DIFFERENCES);
+                }
+            }
+        }
+    }
+
+    let matched = checks.iter().filter(|c| matches!(c.outcome, Outcome::Match)).count();
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("  Checks matched: {}", matched.to_string().green());
+    println!("  Checks differing: {}", (checks.len() - matched).to_string().red());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+}
+
+fn report_json(start: u64, tip: u64, checks: &[Check]) -> Value {
+    let checks: Vec<Value> = checks
+        .iter()
+        .map(|check| {
+            let mut entry = json!({ "method": check.method, "request": check.request });
+            match &check.outcome {
+                Outcome::Match => entry["status"] = json!("match"),
+                Outcome::Error(e) => {
+                    entry["status"] = json!("error");
+                    entry["error"] = json!(e);
+                }
+                Outcome::Mismatch(differences) => {
+                    entry["status"] = json!("mismatch");
+                    entry["differences"] = differences
+                        .iter()
+                        .map(|d| json!({ "path": d.path, "zaino": d.zaino, "lightwalletd": d.lightwalletd }))
+                        .collect();
+                }
+            }
+            entry
+        })
+        .collect();
+
+    let count = |status: &str| checks.iter().filter(|c| c["status"] == status).count();
+    json!({
+        "zaino": INDEXER_URL,
+        "lightwalletd": SIDE_LWD_URL,
+        "blocks": { "start": start, "end": tip },
+        "summary": {
+            "checks": checks.len(),
+            "matched": count("match"),
+            "mismatched": count("mismatch"),
+            "errors": count("error"),
+        },
+        "checks": checks,
+    })
+}
+
+/// Transaction hashes (as sent, little-endian) seen in either block range.
+fn block_txids(a: &Result<Value>, b: &Result<Value>) -> Vec<Vec<u8>> {
+    let mut seen = BTreeSet::new();
+    for blocks in [a, b].into_iter().flatten() {
+        for tx in blocks.as_array().into_iter().flatten().flat_map(|b| b["vtx"].as_array().into_iter().flatten()) {
+            if let Some(hash) = tx["hash"].as_str().and_then(unhash_hex) {
+                seen.insert(hash);
+            }
+        }
+    }
+    seen.into_iter().collect()
+}
+
+// Version, vendor and build details legitimately differ, and the heights
+// move with the miner; the rest describes the chain and must agree.
+fn lightd_info_json(info: &LightdInfo) -> Value {
+    json!({
+        "taddr_support": info.taddr_support,
+        "chain_name": info.chain_name,
+        "sapling_activation_height": info.sapling_activation_height,
+        "consensus_branch_id": info.consensus_branch_id,
+    })
+}
+
+fn compact_block_json(block: &CompactBlock) -> Value {
+    let metadata = block.chain_metadata.as_ref().map(|m| {
+        json!({
+            "sapling_commitment_tree_size": m.sapling_commitment_tree_size,
+            "orchard_commitment_tree_size": m.orchard_commitment_tree_size,
+        })
+    });
+
+    json!({
+        "proto_version": block.proto_version,
+        "height": block.height,
+        "hash": hash_hex(&block.hash),
+        "prev_hash": hash_hex(&block.prev_hash),
+        "time": block.time,
+        "header": hex(&block.header),
+        "vtx": block.vtx.iter().map(compact_tx_json).collect::<Vec<_>>(),
+        "chain_metadata": metadata,
+    })
+}
+
+fn compact_tx_json(tx: &CompactTx) -> Value {
+    let mut fields = Map::new();
+    fields.insert("index".into(), json!(tx.index));
+    fields.insert("hash".into(), json!(hash_hex(&tx.hash)));
+    fields.insert("fee".into(), json!(tx.fee));
+    fields.insert(
+        "spends".into(),
+        tx.spends.iter().map(|s| json!({ "nf": hex(&s.nf) })).collect(),
+    );
+    fields.insert(
+        "outputs".into(),
+        tx.outputs
+            .iter()
+            .map(|o| json!({ "cmu": hex(&o.cmu), "ephemeral_key": hex(&o.ephemeral_key), "ciphertext": hex(&o.ciphertext) }))
+            .collect(),
+    );
+    fields.insert(
+        "actions".into(),
+        tx.actions
+            .iter()
+            .map(|a| {
+                json!({
+                    "nullifier": hex(&a.nullifier),
+                    "cmx": hex(&a.cmx),
+                    "ephemeral_key": hex(&a.ephemeral_key),
+                    "ciphertext": hex(&a.ciphertext),
+                })
+            })
+            .collect(),
+    );
+    Value::Object(fields)
+}
+
+fn tree_state_json(state: &TreeState) -> Value {
+    json!({
+        "network": state.network,
+        "height": state.height,
+        "hash": state.hash,
+        "time": state.time,
+        "sapling_tree": state.sapling_tree,
+        "orchard_tree": state.orchard_tree,
+    })
+}
+
+fn raw_transaction_json(tx: &RawTransaction) -> Value {
+    json!({ "height": tx.height, "data": hex(&tx.data) })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Block and transaction hashes travel little-endian; show them the way
+/// Zebra's RPC and explorers do.
+fn hash_hex(bytes: &[u8]) -> String {
+    let mut reversed = bytes.to_vec();
+    reversed.reverse();
+    hex(&reversed)
+}
+
+/// Inverse of [`hash_hex`].
+fn unhash_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    bytes.reverse();
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_nested_values() {
+        let a = json!({ "height": 5, "vtx": [{ "fee": 1 }, { "fee": 2 }], "only_a": true });
+        let b = json!({ "height": 5, "vtx": [{ "fee": 1 }, { "fee": 3 }, { "fee": 4 }] });
+
+        let mut differences = Vec::new();
+        diff_values("", &a, &b, &mut differences);
+
+        let paths: Vec<&str> = differences.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, ["only_a", "vtx.length", "vtx[1].fee"]);
+        assert_eq!(differences[2].zaino, json!(2));
+        assert_eq!(differences[2].lightwalletd, json!(3));
+
+        let mut none = Vec::new();
+        diff_values("", &a, &a, &mut none);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn round_trips_hashes() {
+        let hash = vec![0x01, 0xab, 0xff];
+        assert_eq!(hash_hex(&hash), "ffab01");
+        assert_eq!(unhash_hex("ffab01"), Some(hash));
+        assert_eq!(unhash_hex("abc"), None);
+    }
+}
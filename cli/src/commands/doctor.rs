@@ -0,0 +1,424 @@
+use crate::config::seed;
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecKitError};
+use crate::utils;
+use colored::*;
+use regex::Regex;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::Duration;
+
+/// Host ports the devnet publishes: Zebra RPC, faucet API, indexer gRPC.
+const PORTS: [(u16, &str); 3] = [(8232, "Zebra RPC"), (8080, "Faucet API"), (9067, "Zaino/lightwalletd gRPC")];
+
+/// Building all images and a regtest chain needs roughly this much room.
+const MIN_FREE_DISK_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// More blocks behind than this means the wallet is not keeping up.
+const MAX_BLOCKS_BEHIND: u64 = 10;
+
+/// Zebra's internal miner finds a regtest block well within this.
+const MAX_TIP_AGE_SECS: i64 = 180;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+    /// The check needs something that isn't running.
+    Skip,
+}
+
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Pass, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Skip, detail: detail.into(), fix: None }
+    }
+}
+
+/// Check the host and the running devnet for the usual causes of a failed
+/// `zeckit up`, with a fix for each problem found.
+pub async fn execute() -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  ZecKit - Doctor".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    let project_dir = utils::project_dir()?;
+    let client = Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+
+    let docker = check_docker();
+    let docker_ok = docker.status == Status::Pass;
+    let mut checks = vec![docker, check_compose()];
+
+    checks.extend(check_ports());
+    checks.push(check_disk(&project_dir));
+
+    if docker_ok {
+        let compose = DockerCompose::new()?;
+        for profile in ["zaino", "lwd"] {
+            checks.push(check_images(&compose, profile));
+        }
+    } else {
+        checks.push(Check::skip("images", "Docker is not available"));
+    }
+
+    checks.push(check_miner_address(&client, &project_dir).await);
+    checks.push(check_wallet_height(&client).await);
+    checks.push(check_miner(&client, &project_dir).await);
+
+    for check in &checks {
+        let (mark, detail) = match check.status {
+            Status::Pass => ("✓".green(), check.detail.normal()),
+            Status::Warn => ("⚠".yellow(), check.detail.yellow()),
+            Status::Fail => ("✗".red(), check.detail.red()),
+            Status::Skip => ("-".dimmed(), check.detail.dimmed()),
+        };
+        println!("  {} {:<16} {}", mark, check.name, detail);
+        if let Some(fix) = &check.fix {
+            println!("    {:<16} {} {}", "", "Fix:".bold(), fix);
+        }
+    }
+
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    let warned = checks.iter().filter(|c| c.status == Status::Warn).count();
+
+    println!();
+    if failed > 0 {
+        return Err(ZecKitError::HealthCheck(format!("{} check(s) failed, {} warning(s)", failed, warned)));
+    }
+    if warned > 0 {
+        println!("{}", format!("No blocking problems found ({} warning(s))", warned).yellow());
+    } else {
+        println!("{}", "✓ Everything looks healthy".green().bold());
+    }
+
+    Ok(())
+}
+
+fn check_docker() -> Check {
+    if !utils::check_docker() {
+        return Check::fail("docker", "docker CLI not found", "Install Docker: https://docs.docker.com/get-docker/");
+    }
+
+    let daemon = Command::new("docker")
+        .args(["info", "--format", "{{.ServerVersion}}"])
+        .output();
+    match daemon {
+        Ok(out) if out.status.success() => {
+            Check::pass("docker", format!("daemon running (server {})", String::from_utf8_lossy(&out.stdout).trim()))
+        }
+        _ => Check::fail(
+            "docker",
+            "Docker daemon is not reachable",
+            "Start Docker (e.g. `sudo systemctl start docker` or open Docker Desktop) and check you can run `docker info`",
+        ),
+    }
+}
+
+fn check_compose() -> Check {
+    if utils::check_docker_compose() {
+        Check::pass("compose", "docker compose plugin available")
+    } else {
+        Check::fail(
+            "compose",
+            "`docker compose` is not available",
+            "Install the Compose plugin: https://docs.docker.com/compose/install/",
+        )
+    }
+}
+
+fn check_ports() -> Vec<Check> {
+    PORTS
+        .iter()
+        .map(|&(port, service)| {
+            if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+                return Check::pass("port", format!("{} ({}) is free", port, service));
+            }
+
+            match container_on_port(port) {
+                Some(name) if name.starts_with("zeckit-") => {
+                    Check::pass("port", format!("{} ({}) in use by {}", port, service, name))
+                }
+                Some(name) => Check::fail(
+                    "port",
+                    format!("{} ({}) in use by container {}", port, service, name),
+                    format!("Stop it with `docker stop {}`", name),
+                ),
+                None => Check::fail(
+                    "port",
+                    format!("{} ({}) in use by another process", port, service),
+                    format!("Find and stop it: `lsof -i :{}` (or `ss -ltnp | grep {}`)", port, port),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn container_on_port(port: u16) -> Option<String> {
+    let out = Command::new("docker")
+        .args(["ps", "--filter", &format!("publish={}", port), "--format", "{{.Names}}"])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .next()
+        .map(str::to_string)
+}
+
+/// Free space where Docker keeps its volumes, or the project directory when
+/// Docker can't say.
+fn check_disk(project_dir: &Path) -> Check {
+    let docker_root = Command::new("docker")
+        .args(["info", "--format", "{{.DockerRootDir}}"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|dir| Path::new(dir).exists());
+    let dir = docker_root.unwrap_or_else(|| project_dir.to_string_lossy().to_string());
+
+    let free = Command::new("df")
+        .args(["-Pk", &dir])
+        .output()
+        .ok()
+        .and_then(|out| parse_df_available(&String::from_utf8_lossy(&out.stdout)));
+
+    match free {
+        Some(bytes) if bytes >= MIN_FREE_DISK_BYTES => {
+            Check::pass("disk", format!("{} free on {}", utils::format_bytes(bytes), dir))
+        }
+        Some(bytes) => Check::warn(
+            "disk",
+            format!("only {} free on {} (want {})", utils::format_bytes(bytes), dir, utils::format_bytes(MIN_FREE_DISK_BYTES)),
+            "Free space with `docker system prune` (add `--volumes` to drop old chain data)",
+        ),
+        None => Check::skip("disk", format!("could not read free space on {}", dir)),
+    }
+}
+
+/// Available bytes from `df -Pk` output (fourth column, in KiB).
+fn parse_df_available(output: &str) -> Option<u64> {
+    output
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()
+        .map(|kib| kib * 1024)
+}
+
+fn check_images(compose: &DockerCompose, profile: &str) -> Check {
+    if compose.images_exist(profile) {
+        Check::pass("images", format!("images for profile {} are present", profile))
+    } else {
+        Check::warn(
+            "images",
+            format!("images for profile {} are missing", profile),
+            format!(
+                "Build them with `docker compose --profile {} build` (`zeckit up --backend {}` also builds them, taking 10-20 minutes)",
+                profile, profile
+            ),
+        )
+    }
+}
+
+async fn check_miner_address(client: &Client, project_dir: &Path) -> Check {
+    let config_path = project_dir.join("docker/configs/zebra.toml");
+    let configured = match fs::read_to_string(&config_path).ok().and_then(|c| miner_address(&c)) {
+        Some(address) => address,
+        None => {
+            return Check::fail(
+                "miner address",
+                "no miner_address in docker/configs/zebra.toml",
+                "Run `zeckit up`, which writes the faucet's address into zebra.toml",
+            )
+        }
+    };
+
+    // Compare with the running faucet, or with the seed `up` would use
+    let (expected, source) = match faucet_get(client, "/address").await {
+        Ok(json) => match json["transparent_address"].as_str() {
+            Some(address) => (address.to_string(), "faucet"),
+            None => return Check::skip("miner address", "faucet returned no transparent address"),
+        },
+        Err(_) => {
+            let phrase = fs::read_to_string(project_dir.join(seed::SEED_FILE))
+                .unwrap_or_else(|_| seed::DEFAULT_SEED.to_string());
+            match seed::derive_transparent_address(phrase.trim()) {
+                Ok(address) => (address, "saved seed"),
+                Err(e) => return Check::skip("miner address", format!("could not derive faucet address: {}", e)),
+            }
+        }
+    };
+
+    if configured == expected {
+        Check::pass("miner address", format!("zebra.toml mines to the {} address {}", source, configured))
+    } else {
+        Check::fail(
+            "miner address",
+            format!("zebra.toml mines to {} but the {} address is {}", configured, source, expected),
+            "Run `zeckit up` again so zebra.toml is rewritten, then restart Zebra; coinbase mined so far went elsewhere",
+        )
+    }
+}
+
+pub fn miner_address(config: &str) -> Option<String> {
+    Regex::new(r#"(?m)^\s*miner_address\s*=\s*"([^"]*)""#)
+        .ok()?
+        .captures(config)
+        .map(|c| c[1].to_string())
+}
+
+async fn check_wallet_height(client: &Client) -> Check {
+    let status = match faucet_get(client, "/sync/status").await {
+        Ok(status) => status,
+        Err(_) => return Check::skip("wallet height", "faucet is not running"),
+    };
+
+    let (wallet, chain) = match (status["wallet_height"].as_u64(), status["chain_height"].as_u64()) {
+        (Some(wallet), Some(chain)) => (wallet, chain),
+        (None, _) => {
+            return Check::warn(
+                "wallet height",
+                "faucet wallet has not synced yet",
+                "Trigger a sync with `curl -X POST http://127.0.0.1:8080/sync`",
+            )
+        }
+        (_, None) => return Check::skip("wallet height", "faucet cannot reach Zebra"),
+    };
+
+    if wallet > chain {
+        Check::fail(
+            "wallet height",
+            format!("wallet at {} is ahead of the chain at {}", wallet, chain),
+            "The chain was reset under the wallet; run `zeckit wallet rescan`",
+        )
+    } else if chain - wallet > MAX_BLOCKS_BEHIND && status["running"] != true {
+        Check::warn(
+            "wallet height",
+            format!("wallet at {} is {} blocks behind the chain at {}", wallet, chain - wallet, chain),
+            "Trigger a sync with `curl -X POST http://127.0.0.1:8080/sync`; if it stays behind, run `zeckit wallet rescan`",
+        )
+    } else {
+        Check::pass("wallet height", format!("wallet at {}, chain at {}", wallet, chain))
+    }
+}
+
+/// Blocks keep coming when Zebra's internal miner (or `up`'s background
+/// miner) is running; judge by the age of the tip.
+async fn check_miner(client: &Client, project_dir: &Path) -> Check {
+    let internal_miner = fs::read_to_string(project_dir.join("docker/configs/zebra.toml"))
+        .map(|c| c.lines().any(|l| l.replace(' ', "") == "internal_miner=true"))
+        .unwrap_or(false);
+
+    let tip = async {
+        let hash = zebra_rpc(client, "getbestblockhash", json!([])).await?;
+        zebra_rpc(client, "getblock", json!([hash, 1])).await
+    };
+    let tip = match tip.await {
+        Ok(tip) => tip,
+        Err(_) => return Check::skip("miner", "Zebra is not running"),
+    };
+
+    let height = tip["height"].as_u64().unwrap_or_default();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let age = tip["time"].as_i64().map(|t| now - t);
+
+    match age {
+        Some(age) if age <= MAX_TIP_AGE_SECS => {
+            Check::pass("miner", format!("last block {} mined {}s ago", height, age.max(0)))
+        }
+        Some(age) => {
+            let fix = if internal_miner {
+                "Zebra's internal miner is enabled but idle; restart it with `docker compose restart zebra`"
+            } else {
+                "Set `internal_miner = true` under [mining] in docker/configs/zebra.toml and restart Zebra, or keep `zeckit up` running for its background miner"
+            };
+            Check::warn("miner", format!("no new block for {}s (tip {})", age, height), fix)
+        }
+        None => Check::skip("miner", "could not read the tip block time"),
+    }
+}
+
+async fn faucet_get(client: &Client, path: &str) -> Result<Value> {
+    let resp = client
+        .get(format!("http://127.0.0.1:8080{}", path))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck(format!("{} returned {}", path, resp.status())));
+    }
+
+    Ok(resp.json().await?)
+}
+
+async fn zebra_rpc(client: &Client, method: &str, params: Value) -> Result<Value> {
+    let resp = client
+        .post("http://127.0.0.1:8232")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params
+        }))
+        .send()
+        .await?;
+
+    let json: Value = resp.json().await?;
+    if !json["error"].is_null() {
+        return Err(ZecKitError::HealthCheck(format!("{} failed: {}", method, json["error"])));
+    }
+
+    Ok(json["result"].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_df_output() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/sda1        102400000  51200000  40960000      56% /var/lib/docker\n";
+        assert_eq!(parse_df_available(output), Some(40960000 * 1024));
+        assert_eq!(parse_df_available(""), None);
+    }
+
+    #[test]
+    fn reads_miner_address() {
+        let config = "[mining]\ninternal_miner = true\nminer_address = \"tmBsTi2xWTjUdEXnuTceL7fecEQKeWaPDJd\"\n";
+        assert_eq!(miner_address(config).as_deref(), Some("tmBsTi2xWTjUdEXnuTceL7fecEQKeWaPDJd"));
+        assert_eq!(miner_address("[mining]\n"), None);
+    }
+}
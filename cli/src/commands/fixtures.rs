@@ -0,0 +1,373 @@
+use crate::error::{Result, ZecKitError};
+use crate::utils;
+use crate::wait::Wait;
+use colored::*;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::Duration;
+
+/// Bumped whenever the layout of the fixture file changes.
+const FIXTURE_VERSION: u32 = 2;
+
+const DEFAULT_OUTPUT: &str = "fixtures/zeckit-fixtures.json";
+
+/// Blocks mined so fresh coinbase matures and can be shielded.
+const COINBASE_MATURITY_BLOCKS: u32 = 101;
+
+/// How long each fixture transaction gets to be mined and synced.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+const FIXTURE_AMOUNT: f64 = 0.1;
+const FIXTURE_MEMO: &str = "ZecKit fixture memo";
+
+/// Write a versioned fixture set: addresses for each receiver combination,
+/// viewing keys, and (unless disabled) one confirmed transaction of each type
+/// with the height it was mined at.
+///
+/// zingolib only puts Orchard and Sapling receivers into the unified
+/// addresses it derives, so combinations with `transparent` can't be
+/// generated; the transparent fixture is a separate `tm...` address.
+pub async fn generate(output: Option<PathBuf>, with_transactions: bool) -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  ZecKit - Generating Fixtures".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    let client = Client::new();
+    let output = output.unwrap_or(utils::project_dir()?.join(DEFAULT_OUTPUT));
+
+    println!("Deriving fixture addresses...");
+    let faucet = faucet_get(&client, "/address").await?;
+    let sapling = new_address(&client, "sapling").await?;
+    let addresses = FixtureAddresses {
+        orchard: address_of_kind(&new_address(&client, "orchard").await?, "unified")?,
+        sapling: address_of_kind(&sapling, "unified")?,
+        sapling_raw: address_of_kind(&sapling, "sapling")?,
+        orchard_sapling: address_of_kind(&new_address(&client, "orchard,sapling").await?, "unified")?,
+        transparent: address_of_kind(&new_address(&client, "transparent").await?, "transparent")?,
+    };
+    println!("✓ Derived 5 addresses");
+
+    println!("Exporting viewing keys...");
+    let keys = faucet_get(&client, "/keys/viewing").await?;
+    println!("✓ Viewing keys exported");
+
+    let mut transactions = Vec::new();
+    if with_transactions {
+        println!();
+        transactions = generate_transactions(&client, &addresses).await?;
+    }
+
+    let generated_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let chain_height = get_block_count(&client).await?;
+    let fixture = fixture_document(generated_at, chain_height, &faucet, &addresses, &keys, transactions);
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&output, serde_json::to_string_pretty(&fixture)?)?;
+
+    println!();
+    println!("{}", "✓ Fixtures written".green().bold());
+    println!("  {}", output.display());
+    println!();
+
+    Ok(())
+}
+
+/// One freshly derived address per receiver combination the faucet can produce.
+struct FixtureAddresses {
+    orchard: String,
+    sapling: String,
+    /// The Sapling receiver of `sapling` as a bare `zregtestsapling` address.
+    sapling_raw: String,
+    orchard_sapling: String,
+    transparent: String,
+}
+
+fn fixture_document(
+    generated_at: u64,
+    chain_height: u64,
+    faucet: &Value,
+    addresses: &FixtureAddresses,
+    keys: &Value,
+    transactions: Vec<Value>,
+) -> Value {
+    json!({
+        "version": FIXTURE_VERSION,
+        "generated_at": generated_at,
+        "network": "regtest",
+        "chain_height": chain_height,
+        "faucet": {
+            "unified_address": faucet["unified_address"],
+            "transparent_address": faucet["transparent_address"]
+        },
+        "addresses": {
+            "orchard": addresses.orchard,
+            "sapling": addresses.sapling,
+            "sapling_raw": addresses.sapling_raw,
+            "orchard_sapling": addresses.orchard_sapling,
+            "transparent": addresses.transparent
+        },
+        "viewing_keys": {
+            "ufvk": keys["ufvk"],
+            "uivk": keys["uivk"],
+            "birthday": keys["birthday"]
+        },
+        "transactions": transactions
+    })
+}
+
+/// Create one confirmed transaction of each type, mining and syncing between
+/// them so every send spends confirmed notes.
+async fn generate_transactions(client: &Client, addresses: &FixtureAddresses) -> Result<Vec<Value>> {
+    let mut transactions = Vec::new();
+
+    println!("[1/5] Mining {} blocks for mature coinbase...", COINBASE_MATURITY_BLOCKS);
+    mine(client, COINBASE_MATURITY_BLOCKS).await?;
+    sync(client).await?;
+
+    println!("[2/5] Shielding coinbase...");
+    let txid = shield(client).await?;
+    transactions.push(json!({ "type": "shielding", "txid": txid, "from": "transparent", "to": "orchard" }));
+    confirm(client, &mut transactions).await?;
+
+    println!("[3/5] Sending z→z...");
+    let txid = send(client, &addresses.orchard, None).await?;
+    transactions.push(json!({
+        "type": "z_to_z", "txid": txid, "to": addresses.orchard, "amount": FIXTURE_AMOUNT
+    }));
+    confirm(client, &mut transactions).await?;
+
+    println!("[4/5] Sending memo-bearing z→z and z→t...");
+    let txid = send(client, &addresses.sapling, Some(FIXTURE_MEMO)).await?;
+    transactions.push(json!({
+        "type": "memo", "txid": txid, "to": addresses.sapling, "amount": FIXTURE_AMOUNT, "memo": FIXTURE_MEMO
+    }));
+    confirm(client, &mut transactions).await?;
+
+    let z_to_t = send(client, &addresses.transparent, None).await?;
+    transactions.push(json!({
+        "type": "z_to_t", "txid": z_to_t, "to": addresses.transparent, "amount": FIXTURE_AMOUNT
+    }));
+    confirm(client, &mut transactions).await?;
+
+    println!("[5/5] Shielding the transparent output (t→z)...");
+    let txid = shield(client).await?;
+    if !spends_output_of(client, &txid, &z_to_t).await? {
+        return Err(ZecKitError::HealthCheck(format!(
+            "Shield {} did not spend the z→t output of {}",
+            txid, z_to_t
+        )));
+    }
+    transactions.push(json!({
+        "type": "t_to_z", "txid": txid, "from": addresses.transparent, "spends": z_to_t, "to": "orchard"
+    }));
+    confirm(client, &mut transactions).await?;
+
+    println!("✓ Created {} confirmed transactions", transactions.len());
+    Ok(transactions)
+}
+
+/// Derive a fresh address and return every entry the faucet made for it.
+async fn new_address(client: &Client, receivers: &str) -> Result<Vec<Value>> {
+    let resp = client
+        .post("http://127.0.0.1:8080/address/new")
+        .query(&[("receivers", receivers)])
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet API call failed: {}", e)))?;
+
+    let json = check_response(resp, "Address derivation").await?;
+    Ok(json["addresses"].as_array().cloned().unwrap_or_default())
+}
+
+fn address_of_kind(derived: &[Value], kind: &str) -> Result<String> {
+    derived
+        .iter()
+        .find(|address| address["kind"] == kind)
+        .and_then(|address| address["address"].as_str())
+        .map(str::to_string)
+        .ok_or_else(|| ZecKitError::HealthCheck(format!("No {} address in faucet response", kind)))
+}
+
+async fn send(client: &Client, address: &str, memo: Option<&str>) -> Result<String> {
+    let resp = client
+        .post("http://127.0.0.1:8080/send")
+        .json(&json!({ "address": address, "amount": FIXTURE_AMOUNT, "memo": memo }))
+        .timeout(Duration::from_secs(120))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet API call failed: {}", e)))?;
+
+    let json = check_response(resp, "Send").await?;
+    json["txid"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ZecKitError::HealthCheck("No txid in send response".into()))
+}
+
+/// Shield the faucet's transparent funds and return the txid. Fails if there
+/// is nothing to shield, which usually means auto-shield got there first.
+async fn shield(client: &Client) -> Result<String> {
+    let resp = client
+        .post("http://127.0.0.1:8080/shield")
+        .timeout(Duration::from_secs(120))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet API call failed: {}", e)))?;
+
+    let json = check_response(resp, "Shield").await?;
+    json["txid"].as_str().map(str::to_string).ok_or_else(|| {
+        ZecKitError::HealthCheck(format!(
+            "Nothing to shield ({}); start the devnet with AUTO_SHIELD_ENABLED=false to generate fixtures",
+            json["message"].as_str().unwrap_or("no txid in response")
+        ))
+    })
+}
+
+/// Whether any input of `txid` spends an output of `funding_txid`.
+async fn spends_output_of(client: &Client, txid: &str, funding_txid: &str) -> Result<bool> {
+    let tx = zebra_rpc(client, "getrawtransaction", json!([txid, 1])).await?;
+    Ok(tx["vin"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|input| input["txid"] == funding_txid))
+}
+
+/// Wait for the transaction just recorded to be mined and seen by the
+/// faucet wallet, and record the height it was mined at.
+async fn confirm(client: &Client, transactions: &mut [Value]) -> Result<()> {
+    let tx = transactions.last_mut().expect("a transaction was just recorded");
+    let txid = tx["txid"].as_str().unwrap_or_default().to_string();
+    let height = Wait::new(client, CONFIRMATION_TIMEOUT).mining().for_confirmed(&txid).await?;
+    tx["height"] = json!(height);
+    Ok(())
+}
+
+async fn sync(client: &Client) -> Result<()> {
+    let resp = client
+        .post("http://127.0.0.1:8080/sync")
+        .timeout(Duration::from_secs(180))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet sync failed: {}", e)))?;
+
+    check_response(resp, "Wallet sync").await?;
+    Ok(())
+}
+
+async fn faucet_get(client: &Client, path: &str) -> Result<Value> {
+    let resp = client
+        .get(format!("http://127.0.0.1:8080{}", path))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet API call failed: {}", e)))?;
+
+    check_response(resp, path).await
+}
+
+async fn check_response(resp: reqwest::Response, what: &str) -> Result<Value> {
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(ZecKitError::HealthCheck(format!("{} failed ({}): {}", what, status, body)));
+    }
+
+    Ok(resp.json().await?)
+}
+
+async fn zebra_rpc(client: &Client, method: &str, params: Value) -> Result<Value> {
+    let resp = client
+        .post("http://127.0.0.1:8232")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params
+        }))
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await?;
+
+    let json: Value = resp.json().await?;
+    if !json["error"].is_null() {
+        return Err(ZecKitError::HealthCheck(format!("{} failed: {}", method, json["error"])));
+    }
+
+    Ok(json["result"].clone())
+}
+
+async fn mine(client: &Client, blocks: u32) -> Result<()> {
+    zebra_rpc(client, "generate", json!([blocks])).await?;
+    Ok(())
+}
+
+async fn get_block_count(client: &Client) -> Result<u64> {
+    zebra_rpc(client, "getblockcount", json!([]))
+        .await?
+        .as_u64()
+        .ok_or_else(|| ZecKitError::HealthCheck("Invalid block count response".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses() -> FixtureAddresses {
+        FixtureAddresses {
+            orchard: "uregtest1orchard".into(),
+            sapling: "uregtest1sapling".into(),
+            sapling_raw: "zregtestsapling1raw".into(),
+            orchard_sapling: "uregtest1both".into(),
+            transparent: "tmTransparent".into(),
+        }
+    }
+
+    #[test]
+    fn fixture_layout() {
+        let faucet = json!({ "unified_address": "uregtest1faucet", "transparent_address": "tmFaucet", "extra": 1 });
+        let keys = json!({ "ufvk": "uviewregtest1", "uivk": "uivkregtest1", "birthday": 1, "account": 0 });
+        let transactions = vec![json!({ "type": "shielding", "txid": "ab", "height": 103 })];
+
+        let fixture = fixture_document(1_700_000_000, 210, &faucet, &addresses(), &keys, transactions);
+
+        assert_eq!(fixture, json!({
+            "version": FIXTURE_VERSION,
+            "generated_at": 1_700_000_000u64,
+            "network": "regtest",
+            "chain_height": 210,
+            "faucet": { "unified_address": "uregtest1faucet", "transparent_address": "tmFaucet" },
+            "addresses": {
+                "orchard": "uregtest1orchard",
+                "sapling": "uregtest1sapling",
+                "sapling_raw": "zregtestsapling1raw",
+                "orchard_sapling": "uregtest1both",
+                "transparent": "tmTransparent"
+            },
+            "viewing_keys": { "ufvk": "uviewregtest1", "uivk": "uivkregtest1", "birthday": 1 },
+            "transactions": [{ "type": "shielding", "txid": "ab", "height": 103 }]
+        }));
+    }
+
+    #[test]
+    fn picks_addresses_by_kind() {
+        let derived = vec![
+            json!({ "address": "uregtest1sapling", "kind": "unified" }),
+            json!({ "address": "zregtestsapling1raw", "kind": "sapling" }),
+        ];
+
+        assert_eq!(address_of_kind(&derived, "unified").unwrap(), "uregtest1sapling");
+        assert_eq!(address_of_kind(&derived, "sapling").unwrap(), "zregtestsapling1raw");
+        assert!(address_of_kind(&derived, "transparent").is_err());
+    }
+}
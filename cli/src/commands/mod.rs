@@ -1,4 +1,8 @@
 pub mod up;
 pub mod down;
 pub mod status;
-pub mod test;
\ No newline at end of file
+pub mod test;
+pub mod wallet;
+pub mod fixtures;
+pub mod reorg;
+pub mod doctor;pub mod compare;
@@ -0,0 +1,289 @@
+use crate::docker::compose::DockerCompose;
+use crate::error::{Result, ZecKitError};
+use crate::wait::{tx_state, TxState, Wait};
+use colored::*;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::io::{self, Write};
+use tokio::time::{sleep, Duration, Instant};
+
+const MAIN_RPC: &str = "http://127.0.0.1:8232";
+const FORK_RPC: &str = "http://127.0.0.1:8234";
+const FORK_SERVICE: &str = "zebra-fork";
+
+/// Zebra finalizes blocks deeper than this, so they can't be rolled back.
+const MAX_REORG_DEPTH: u64 = 99;
+
+/// Attempts at overtaking the main chain, which keeps mining meanwhile.
+const MAX_FORK_ROUNDS: u32 = 5;
+
+const FORK_STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
+const WALLET_RECOVERY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// A faucet transaction that was mined in a block the reorg orphaned.
+struct Orphaned {
+    txid: String,
+    old_height: u64,
+    now: TxState,
+}
+
+/// Replace the last `depth` blocks of the main chain with a longer fork.
+///
+/// The fork is mined on an isolated `zebra-fork` node that first gets a copy
+/// of the shared history; its blocks are then submitted to the main node,
+/// which switches to them because they carry more work.
+pub async fn execute(depth: u64, keep_fork: bool) -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  ZecKit - Chain Reorganization".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    if depth == 0 || depth > MAX_REORG_DEPTH {
+        return Err(ZecKitError::Config(format!(
+            "--depth must be between 1 and {} (Zebra finalizes deeper blocks)",
+            MAX_REORG_DEPTH
+        )));
+    }
+
+    let compose = DockerCompose::new()?;
+    let result = reorg(&compose, depth).await;
+
+    if keep_fork {
+        println!("Fork node left running on {}", FORK_RPC);
+    } else if let Err(e) = compose.remove(FORK_SERVICE) {
+        println!("{}", format!("Warning: could not remove the fork node: {}", e).yellow());
+    }
+
+    result
+}
+
+async fn reorg(compose: &DockerCompose, depth: u64) -> Result<()> {
+    let client = Client::new();
+
+    let old_tip = block_count(&client, MAIN_RPC).await?;
+    if old_tip <= depth {
+        return Err(ZecKitError::Config(format!(
+            "Chain is only {} blocks high; cannot reorg {} blocks",
+            old_tip, depth
+        )));
+    }
+    let old_hash = best_block_hash(&client, MAIN_RPC).await?;
+    let fork_point = old_tip - depth;
+
+    println!("Old tip: {} ({})", old_tip, old_hash);
+    println!("Forking at height {} ({} blocks orphaned)", fork_point, depth);
+    println!();
+
+    // Remember where the faucet's transactions are before the reorg
+    let mined_before = match faucet_txids(&client).await {
+        Ok(txids) => {
+            let mut mined = Vec::new();
+            for txid in txids {
+                if let Ok(TxState::Mined(height)) = tx_state(&client, &txid).await {
+                    mined.push((txid, height));
+                }
+            }
+            mined
+        }
+        Err(e) => {
+            println!("{}", format!("Warning: could not read faucet history: {}", e).yellow());
+            Vec::new()
+        }
+    };
+
+    println!("[1/4] Starting fork node...");
+    compose.recreate(FORK_SERVICE)?;
+    wait_for_rpc(&client, FORK_RPC).await?;
+
+    println!("[2/4] Copying {} shared blocks to the fork node...", fork_point);
+    copy_blocks(&client, MAIN_RPC, FORK_RPC, 1..=fork_point).await?;
+    println!();
+
+    println!("[3/4] Mining a longer fork and submitting it...");
+    let mut switched = false;
+    for round in 1..=MAX_FORK_ROUNDS {
+        let main_tip = block_count(&client, MAIN_RPC).await?;
+        let fork_tip = block_count(&client, FORK_RPC).await?;
+        if fork_tip <= main_tip {
+            rpc(&client, FORK_RPC, "generate", json!([main_tip - fork_tip + 1])).await?;
+        }
+
+        let fork_tip = block_count(&client, FORK_RPC).await?;
+        copy_blocks(&client, FORK_RPC, MAIN_RPC, fork_point + 1..=fork_tip).await?;
+        println!();
+
+        if block_hash(&client, MAIN_RPC, fork_point + 1).await?
+            == block_hash(&client, FORK_RPC, fork_point + 1).await?
+        {
+            switched = true;
+            break;
+        }
+        println!("  Main chain is still ahead after round {}; mining more", round);
+    }
+
+    if !switched {
+        return Err(ZecKitError::HealthCheck(format!(
+            "Main node did not switch to the fork after {} rounds",
+            MAX_FORK_ROUNDS
+        )));
+    }
+
+    let new_tip = block_count(&client, MAIN_RPC).await?;
+    let new_hash = best_block_hash(&client, MAIN_RPC).await?;
+
+    println!("[4/4] Waiting for the faucet wallet to follow the new chain...");
+    let wallet = Wait::new(&client, WALLET_RECOVERY_TIMEOUT).for_wallet_at_tip().await;
+
+    let mut orphaned = Vec::new();
+    for (txid, old_height) in mined_before.into_iter().filter(|(_, h)| *h > fork_point) {
+        let now = tx_state(&client, &txid).await.unwrap_or(TxState::Unknown);
+        orphaned.push(Orphaned { txid, old_height, now });
+    }
+
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("  Common ancestor: {}", fork_point);
+    println!("  Old tip: {} ({})", old_tip, old_hash);
+    println!("  New tip: {} ({})", new_tip, new_hash);
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    if orphaned.is_empty() {
+        println!("No faucet transactions were in the orphaned blocks");
+    } else {
+        println!("Faucet transactions un-mined by the reorg:");
+        for tx in &orphaned {
+            let now = match tx.now {
+                TxState::Mined(height) => format!("re-mined at {}", height),
+                TxState::InMempool => "back in the mempool".to_string(),
+                TxState::Unknown => "dropped".to_string(),
+            };
+            println!("  {} (was at {}): {}", tx.txid, tx.old_height, now);
+        }
+    }
+    println!();
+
+    match wallet {
+        Ok(height) => {
+            println!("{}", format!("✓ Faucet wallet recovered (synced to {})", height).green());
+            Ok(())
+        }
+        Err(e) => {
+            println!("{}", "✗ Faucet wallet did not recover".red());
+            println!("  Rebuild it with: zeckit wallet rescan --from-height {}", fork_point);
+            Err(e)
+        }
+    }
+}
+
+/// Copy blocks by height from one node to another with `submitblock`.
+async fn copy_blocks(
+    client: &Client,
+    from: &str,
+    to: &str,
+    heights: std::ops::RangeInclusive<u64>,
+) -> Result<()> {
+    let total = heights.clone().count();
+
+    for (i, height) in heights.enumerate() {
+        let block = rpc(client, from, "getblock", json!([height.to_string(), 0])).await?;
+        let hex = block
+            .as_str()
+            .ok_or_else(|| ZecKitError::HealthCheck(format!("No raw block at height {}", height)))?;
+
+        let result = rpc(client, to, "submitblock", json!([hex])).await?;
+        match result.as_str() {
+            None | Some("duplicate") => {}
+            Some(reason) => {
+                return Err(ZecKitError::HealthCheck(format!(
+                    "Block {} rejected by {}: {}",
+                    height, to, reason
+                )));
+            }
+        }
+
+        if (i + 1) % 10 == 0 || i + 1 == total {
+            print!("\r  {} / {} blocks", i + 1, total);
+            io::stdout().flush().ok();
+        }
+    }
+
+    Ok(())
+}
+
+async fn wait_for_rpc(client: &Client, url: &str) -> Result<()> {
+    let deadline = Instant::now() + FORK_STARTUP_TIMEOUT;
+
+    while Instant::now() < deadline {
+        if block_count(client, url).await.is_ok() {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+
+    Err(ZecKitError::ServiceNotReady(format!("Fork node RPC at {} not ready", url)))
+}
+
+async fn faucet_txids(client: &Client) -> Result<Vec<String>> {
+    let resp = client
+        .get("http://127.0.0.1:8080/history?limit=1000")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck(format!("/history returned {}", resp.status())));
+    }
+
+    let json: Value = resp.json().await?;
+    Ok(json["transactions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|tx| tx["txid"].as_str().map(str::to_string))
+        .collect())
+}
+
+async fn rpc(client: &Client, url: &str, method: &str, params: Value) -> Result<Value> {
+    let resp = client
+        .post(url)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params
+        }))
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await?;
+
+    let json: Value = resp.json().await?;
+    if !json["error"].is_null() {
+        return Err(ZecKitError::HealthCheck(format!("{} failed: {}", method, json["error"])));
+    }
+
+    Ok(json["result"].clone())
+}
+
+async fn block_count(client: &Client, url: &str) -> Result<u64> {
+    rpc(client, url, "getblockcount", json!([]))
+        .await?
+        .as_u64()
+        .ok_or_else(|| ZecKitError::HealthCheck("Invalid block count response".into()))
+}
+
+async fn best_block_hash(client: &Client, url: &str) -> Result<String> {
+    rpc(client, url, "getbestblockhash", json!([]))
+        .await?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ZecKitError::HealthCheck("Invalid best block hash response".into()))
+}
+
+async fn block_hash(client: &Client, url: &str, height: u64) -> Result<String> {
+    rpc(client, url, "getblockhash", json!([height]))
+        .await?
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ZecKitError::HealthCheck(format!("No block hash at height {}", height)))
+}
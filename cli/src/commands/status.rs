@@ -1,4 +1,5 @@
 use crate::docker::compose::DockerCompose;
+use crate::docker::health::HealthChecker;
 use crate::error::Result;
 use colored::*;
 use reqwest::Client;
@@ -36,9 +37,41 @@ pub async fn execute() -> Result<()> {
     print_service_status(&client, "Faucet", "http://127.0.0.1:8080/stats").await;
     
     println!();
+    if let Ok((_, report)) = HealthChecker::new().faucet_readiness().await {
+        print_readiness(&report);
+        println!();
+    }
     Ok(())
 }
 
+/// Print the faucet's `/readyz` report, one line per component.
+pub fn print_readiness(report: &Value) {
+    let ready = report["status"] == "ready";
+    println!(
+        "  Faucet readiness: {}",
+        if ready { "ready".green() } else { "not ready".yellow() }
+    );
+
+    for (name, component) in report["components"].as_object().into_iter().flatten() {
+        let mark = if component["status"] == "ok" { "✓".green() } else { "✗".red() };
+        println!("    {} {:<10} {}", mark, name, describe_component(component).dimmed());
+    }
+}
+
+fn describe_component(component: &Value) -> String {
+    if let Some(error) = component["error"].as_str() {
+        return error.to_string();
+    }
+    component
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, value)| *key != "status" && !value.is_null())
+        .map(|(key, value)| format!("{}={}", key, value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 async fn print_service_status(client: &Client, name: &str, url: &str) {
     match client.get(url).send().await {
         Ok(resp) if resp.status().is_success() => {
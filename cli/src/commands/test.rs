@@ -1,458 +1,182 @@
-use crate::error::Result;
+use crate::error::{Result, ZecKitError};
+use crate::testing::registry::Registry;
+use crate::testing::report::ReportSpec;
+use crate::testing::{custom, Outcome, TestBody, TestCase, TestContext, TestResult, TestStatus};
+use crate::utils;
 use colored::*;
-use reqwest::Client;
-use serde_json::Value;
-use tokio::time::{sleep, Duration};
-
-pub async fn execute() -> Result<()> {
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("{}", "  ZecKit - Running Smoke Tests".cyan().bold());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
+use std::io::{self, Write};
+use std::time::Instant;
+
+pub struct TestOptions {
+    /// Run only tests whose name contains, or tag equals, one of these.
+    pub filters: Vec<String>,
+    /// Leave out tests matching any of these.
+    pub skips: Vec<String>,
+    /// Print the selected tests instead of running them.
+    pub list: bool,
+    /// Show details of passing tests too.
+    pub verbose: bool,
+    /// Count skipped tests as failures.
+    pub strict: bool,
+    /// Machine-readable reports to write after the run.
+    pub reports: Vec<ReportSpec>,
+}
 
-    let client = Client::new();
-    let mut passed = 0;
-    let mut failed = 0;
+pub async fn execute(options: TestOptions) -> Result<()> {
+    let registry = Registry::load(&utils::project_dir()?)?;
+    let selected = registry.select(&options.filters, &options.skips);
 
-    // Test 1: Zebra RPC
-    print!("  [1/6] Zebra RPC connectivity... ");
-    match test_zebra_rpc(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
-        }
+    if options.list {
+        print_list(&registry, &selected);
+        return Ok(());
     }
 
-    // Test 2: Faucet Health
-    print!("  [2/6] Faucet health check... ");
-    match test_faucet_health(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    // A report on stdout (e.g. `--report tap`) must not be mixed with progress output
+    let quiet = options.reports.iter().any(|r| r.path.is_none());
 
-    // Test 3: Faucet Address
-    print!("  [3/6] Faucet address retrieval... ");
-    match test_faucet_address(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
-        }
+    if !quiet {
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+        println!("{}", "  ZecKit - Running Smoke Tests".cyan().bold());
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+        println!();
     }
 
-    // Test 4: Wallet Sync
-    print!("  [4/6] Wallet sync capability... ");
-    match test_wallet_sync(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
-        }
+    if selected.is_empty() {
+        return Err(ZecKitError::Config("No tests match the given --filter/--skip".into()));
     }
 
-    // Test 5: Wallet balance and shield (using API endpoints)
-    print!("  [5/6] Wallet balance and shield... ");
-    match test_wallet_shield(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
-        }
-    }
+    let mut ctx = TestContext::new();
+    let mut results: Vec<TestResult> = Vec::new();
+    let total = selected.len();
+    let suite_start = Instant::now();
 
-    // Test 6: Shielded send (E2E golden flow)
-    print!("  [6/6] Shielded send (E2E)... ");
-    match test_shielded_send(&client).await {
-        Ok(_) => {
-            println!("{}", "PASS".green());
-            passed += 1;
-        }
-        Err(e) => {
-            println!("{} {}", "FAIL".red(), e);
-            failed += 1;
+    for (i, test) in selected.iter().enumerate() {
+        if !quiet {
+            print!("  [{}/{}] {}... ", i + 1, total, test.description_or_name());
+            io::stdout().flush().ok();
         }
-    }
-
-    println!();
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!("  Tests passed: {}", passed.to_string().green());
-    println!("  Tests failed: {}", failed.to_string().red());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
-    println!();
-
-    if failed > 0 {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            format!("{} test(s) failed", failed)
-        ));
-    }
-
-    Ok(())
-}
-
-async fn test_zebra_rpc(client: &Client) -> Result<()> {
-    let resp = client
-        .post("http://127.0.0.1:8232")
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": "test",
-            "method": "getblockcount",
-            "params": []
-        }))
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Zebra RPC not responding".into()
-        ));
-    }
-
-    Ok(())
-}
-
-async fn test_faucet_health(client: &Client) -> Result<()> {
-    let resp = client
-        .get("http://127.0.0.1:8080/health")
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Faucet health check failed".into()
-        ));
-    }
-
-    let json: Value = resp.json().await?;
-    
-    // Verify key health fields
-    if json.get("status").and_then(|v| v.as_str()) != Some("healthy") {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Faucet not reporting healthy status".into()
-        ));
-    }
-
-    Ok(())
-}
 
-async fn test_faucet_address(client: &Client) -> Result<()> {
-    let resp = client
-        .get("http://127.0.0.1:8080/address")
-        .send()
-        .await?;
+        let start = Instant::now();
+        let status = match failed_dependency(test, &results) {
+            Some(dep) => TestStatus::Blocked(format!("dependency {} did not pass", dep)),
+            None => match run_test(&mut ctx, test).await {
+                Ok(Outcome::Passed) => TestStatus::Passed,
+                Ok(Outcome::Skipped(reason)) if options.strict => {
+                    TestStatus::Failed(format!("skipped (--strict): {}", reason))
+                }
+                Ok(Outcome::Skipped(reason)) => TestStatus::Skipped(reason),
+                Err(e) => TestStatus::Failed(e.to_string()),
+            },
+        };
+        let duration = start.elapsed();
+        let (notes, context) = ctx.take_output();
+
+        if !quiet {
+            match &status {
+                TestStatus::Passed => println!("{}", "PASS".green()),
+                TestStatus::Failed(message) => println!("{} {}", "FAIL".red(), message),
+                TestStatus::Skipped(reason) => println!("{} {}", "SKIP".yellow(), reason),
+                TestStatus::Blocked(reason) => println!("{} {}", "BLOCKED".yellow(), reason),
+            }
+            if options.verbose || matches!(status, TestStatus::Failed(_) | TestStatus::Skipped(_)) {
+                for line in &notes {
+                    println!("        {}", line.dimmed());
+                }
+            }
+        }
 
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Could not get faucet address".into()
-        ));
+        results.push(TestResult {
+            name: test.name.clone(),
+            description: test.description.clone(),
+            source: test.source(),
+            tags: test.tags.clone(),
+            status,
+            duration,
+            notes,
+            context,
+        });
     }
 
-    let json: Value = resp.json().await?;
-    
-    // Verify both address types are present
-    if json.get("unified_address").is_none() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Missing unified address in response".into()
-        ));
-    }
-    
-    if json.get("transparent_address").is_none() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Missing transparent address in response".into()
-        ));
+    let suite_duration = suite_start.elapsed();
+    for report in &options.reports {
+        report.write(&results, suite_duration)?;
     }
 
-    Ok(())
-}
-async fn test_wallet_sync(client: &Client) -> Result<()> {
-    let resp = client
-        .post("http://127.0.0.1:8080/sync")
-        .send()
-        .await?;
+    let passed = results.iter().filter(|r| r.status == TestStatus::Passed).count();
+    let failed = results.iter().filter(|r| matches!(r.status, TestStatus::Failed(_))).count();
+    let skipped = results.iter().filter(|r| matches!(r.status, TestStatus::Skipped(_))).count();
+    let blocked = results.iter().filter(|r| matches!(r.status, TestStatus::Blocked(_))).count();
 
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Wallet sync failed".into()
-        ));
+    if !quiet {
+        println!();
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+        println!("  Tests passed: {}", passed.to_string().green());
+        println!("  Tests failed: {}", failed.to_string().red());
+        if skipped > 0 {
+            println!("  Tests skipped: {}", skipped.to_string().yellow());
+        }
+        if blocked > 0 {
+            println!("  Tests blocked: {}", blocked.to_string().yellow());
+        }
+        println!("  Duration: {:.1}s", suite_duration.as_secs_f64());
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+        for report in options.reports.iter().filter_map(|r| r.path.as_ref()) {
+            println!("  Report: {}", report.display());
+        }
+        println!();
     }
 
-    let json: Value = resp.json().await?;
-    
-    if json.get("status").and_then(|v| v.as_str()) != Some("synced") {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Wallet sync did not complete successfully".into()
-        ));
+    // Blocked tests always trace back to a failure or a (non-strict) skip
+    if failed > 0 {
+        return Err(ZecKitError::HealthCheck(format!(
+            "{} test(s) failed, {} blocked",
+            failed, blocked
+        )));
     }
 
     Ok(())
 }
 
-async fn test_wallet_shield(client: &Client) -> Result<()> {
-    println!();
-    
-    // Step 1: Get current wallet balance via API
-    println!("    Checking wallet balance via API...");
-    let balance = get_wallet_balance_via_api(client).await?;
-    
-    let transparent_before = balance.transparent;
-    let orchard_before = balance.orchard;
-    
-    println!("    Transparent: {} ZEC", transparent_before);
-    println!("    Orchard: {} ZEC", orchard_before);
-    
-    // Step 2: If we have transparent funds >= 0.001 ZEC (accounting for fee), shield them
-    let min_shield_amount = 0.0002; // Need at least fee + some amount
-    
-    if transparent_before >= min_shield_amount {
-        println!("    Shielding {} ZEC to Orchard via API...", transparent_before);
-        
-        // Call the shield endpoint
-        let shield_resp = client
-            .post("http://127.0.0.1:8080/shield")
-            .send()
-            .await?;
-        
-        if !shield_resp.status().is_success() {
-            let error_text = shield_resp.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(crate::error::ZecKitError::HealthCheck(
-                format!("Shield API call failed: {}", error_text)
-            ));
+async fn run_test(ctx: &mut TestContext, test: &TestCase) -> Result<Outcome> {
+    match &test.body {
+        TestBody::Builtin(run) => run(ctx).await,
+        TestBody::Command { command, timeout_secs } => {
+            custom::run_command(ctx, command, *timeout_secs).await
         }
-        
-        let shield_json: Value = shield_resp.json().await?;
-        
-        // Check shield status
-        let status = shield_json.get("status").and_then(|v| v.as_str()).unwrap_or("unknown");
-        
-        match status {
-            "shielded" => {
-                if let Some(txid) = shield_json.get("txid").and_then(|v| v.as_str()) {
-                    println!("    Shield transaction broadcast!");
-                    println!("    TXID: {}...", &txid[..16.min(txid.len())]);
-                }
-                
-                // Wait for transaction to be mined
-                println!("    Waiting for transaction to confirm...");
-                sleep(Duration::from_secs(30)).await;
-                
-                // Sync wallet to see new balance
-                println!("    Syncing wallet to update balance...");
-                let _ = client.post("http://127.0.0.1:8080/sync").send().await;
-                sleep(Duration::from_secs(5)).await;
-                
-                // Check balance after shielding
-                let balance_after = get_wallet_balance_via_api(client).await?;
-                
-                println!("    Balance after shield:");
-                println!("    Transparent: {} ZEC (was {})", balance_after.transparent, transparent_before);
-                println!("    Orchard: {} ZEC (was {})", balance_after.orchard, orchard_before);
-                
-                // Verify shield worked (balance changed)
-                if balance_after.orchard > orchard_before || balance_after.transparent < transparent_before {
-                    println!("    Shield successful - funds moved!");
-                } else {
-                    println!("    Shield transaction sent but balance not yet updated");
-                    println!("    (May need more time to confirm)");
-                }
-                
-                println!();
-                print!("  [5/6] Wallet balance and shield... ");
-                Ok(())
-            }
-            "no_funds" => {
-                println!("    No transparent funds to shield (already shielded)");
-                println!();
-                print!("  [5/6] Wallet balance and shield... ");
-                Ok(())
-            }
-            _ => {
-                println!("    Shield status: {}", status);
-                if let Some(msg) = shield_json.get("message").and_then(|v| v.as_str()) {
-                    println!("    Message: {}", msg);
-                }
-                println!();
-                print!("  [5/6] Wallet balance and shield... ");
-                Ok(())
-            }
-        }
-        
-    } else if orchard_before >= 0.001 {
-        println!("    Wallet already has {} ZEC shielded in Orchard - PASS", orchard_before);
-        println!();
-        print!("  [5/6] Wallet balance and shield... ");
-        Ok(())
-        
-    } else if transparent_before > 0.0 {
-        println!("    Wallet has {} ZEC transparent (too small to shield)", transparent_before);
-        println!("    Need at least {} ZEC to cover shield + fee", min_shield_amount);
-        println!("    SKIP (insufficient balance)");
-        println!();
-        print!("  [5/6] Wallet balance and shield... ");
-        Ok(())
-        
-    } else {
-        println!("    No balance found");
-        println!("    SKIP (needs mining to complete)");
-        println!();
-        print!("  [5/6] Wallet balance and shield... ");
-        Ok(())
     }
 }
 
-#[derive(Debug)]
-struct WalletBalance {
-    transparent: f64,
-    orchard: f64,
+/// First dependency that ran in this session without passing. Dependencies
+/// left out with `--skip` don't block.
+fn failed_dependency<'a>(test: &'a TestCase, results: &[TestResult]) -> Option<&'a str> {
+    test.depends_on
+        .iter()
+        .find(|dep| {
+            results
+                .iter()
+                .any(|r| &r.name == *dep && r.status != TestStatus::Passed)
+        })
+        .map(String::as_str)
 }
 
-/// Get wallet balance using the /stats endpoint
-async fn get_wallet_balance_via_api(client: &Client) -> Result<WalletBalance> {
-    let resp = client
-        .get("http://127.0.0.1:8080/stats")
-        .send()
-        .await?;
-
-    if !resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Failed to get balance from stats endpoint".into()
-        ));
-    }
+fn print_list(registry: &Registry, selected: &[&TestCase]) {
+    println!("{}", "Registered tests (* = selected):".bold());
+    println!();
 
-    let json: Value = resp.json().await?;
-    
-    // Extract balance from stats endpoint
-    // Stats should have fields like: current_balance, transparent_balance, orchard_balance
-    let transparent = json.get("transparent_balance")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
-    
-    let orchard = json.get("orchard_balance")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.0);
-    
-    Ok(WalletBalance {
-        transparent,
-        orchard,
-    })
-}
+    for test in registry.tests() {
+        let mark = if selected.iter().any(|s| s.name == test.name) { "*" } else { " " };
+        println!("  {} {:<20} {}", mark, test.name.cyan(), test.description_or_name());
 
-/// Test 6: Shielded Send (E2E Golden Flow)
-/// This is the key test for Milestone 2 - sending shielded funds to another wallet
-async fn test_shielded_send(client: &Client) -> Result<()> {
-    println!();
-    
-    // Step 1: Check faucet has shielded funds
-    println!("    Checking faucet Orchard balance...");
-    let balance = get_wallet_balance_via_api(client).await?;
-    
-    if balance.orchard < 0.1 {
-        println!("    Faucet has insufficient Orchard balance: {} ZEC", balance.orchard);
-        println!("    SKIP (need at least 0.1 ZEC shielded)");
-        println!();
-        print!("  [6/6] Shielded send (E2E)... ");
-        return Ok(());
-    }
-    
-    println!("    Faucet Orchard balance: {} ZEC", balance.orchard);
-    
-    // ADD THIS: Extra sync to ensure wallet can spend the funds
-    println!("    Syncing wallet to ensure spendable balance...");
-    let _ = client.post("http://127.0.0.1:8080/sync").send().await;
-    sleep(Duration::from_secs(10)).await;
-    
-    // Step 2: Get a test recipient address (using faucet's own UA for simplicity)
-    println!("    Getting recipient address...");
-    let addr_resp = client
-        .get("http://127.0.0.1:8080/address")
-        .send()
-        .await?;
-    
-    if !addr_resp.status().is_success() {
-        return Err(crate::error::ZecKitError::HealthCheck(
-            "Failed to get recipient address".into()
-        ));
-    }
-    
-    let addr_json: Value = addr_resp.json().await?;
-    let recipient_address = addr_json.get("unified_address")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| crate::error::ZecKitError::HealthCheck(
-            "No unified address in response".into()
-        ))?;
-    
-    println!("    Recipient: {}...", &recipient_address[..20.min(recipient_address.len())]);
-    
-    // Step 3: Perform shielded send
-    let send_amount = 0.05; // Send 0.05 ZEC
-    println!("    Sending {} ZEC (shielded)...", send_amount);
-    
-    let send_resp = client
-        .post("http://127.0.0.1:8080/send")
-        .json(&serde_json::json!({
-            "address": recipient_address,
-            "amount": send_amount,
-            "memo": "ZecKit smoke test - shielded send"
-        }))
-        .send()
-        .await?;
-    
-    if !send_resp.status().is_success() {
-        let error_text = send_resp.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(crate::error::ZecKitError::HealthCheck(
-            format!("Shielded send failed: {}", error_text)
-        ));
-    }
-    
-    let send_json: Value = send_resp.json().await?;
-    
-    // Step 4: Verify transaction
-    let status = send_json.get("status").and_then(|v| v.as_str());
-    
-    if status == Some("sent") {
-        if let Some(txid) = send_json.get("txid").and_then(|v| v.as_str()) {
-            println!("    ✓ Shielded send successful!");
-            println!("    TXID: {}...", &txid[..16.min(txid.len())]);
-        }
-        
-        if let Some(new_balance) = send_json.get("orchard_balance").and_then(|v| v.as_f64()) {
-            println!("    New Orchard balance: {} ZEC (was {})", new_balance, balance.orchard);
+        let mut details = vec![format!("source: {}", test.source())];
+        if !test.tags.is_empty() {
+            details.push(format!("tags: {}", test.tags.join(", ")));
         }
-        
-        println!("    ✓ E2E Golden Flow Complete:");
-        println!("      - Faucet had shielded funds (Orchard)");
-        println!("      - Sent {} ZEC to recipient UA", send_amount);
-        println!("      - Transaction broadcast successfully");
-        
-        println!();
-        print!("  [6/6] Shielded send (E2E)... ");
-        Ok(())
-    } else {
-        println!("    Unexpected status: {:?}", status);
-        if let Some(msg) = send_json.get("message").and_then(|v| v.as_str()) {
-            println!("    Message: {}", msg);
+        if !test.depends_on.is_empty() {
+            details.push(format!("depends on: {}", test.depends_on.join(", ")));
         }
-        println!();
-        print!("  [6/6] Shielded send (E2E)... ");
-        Err(crate::error::ZecKitError::HealthCheck(
-            "Shielded send did not complete as expected".into()
-        ))
+        println!("    {:<20} {}", "", details.join("  ").dimmed());
     }
-}
\ No newline at end of file
+
+    println!();
+    println!("  {} of {} selected", selected.len(), registry.tests().len());
+}
@@ -1,26 +1,61 @@
+use crate::config::activation::{ActivationHeights, ZEBRA_CONFIGS};
+use crate::config::seed;
 use crate::docker::compose::DockerCompose;
 use crate::docker::health::HealthChecker;
 use crate::error::{Result, ZecKitError};
+use crate::indexer::{INDEXER_URL, SIDE_LWD_URL};
+use crate::wait::Wait;
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
 use serde_json::json;
 use std::fs;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use tokio::time::{sleep, Duration};
 
 const MAX_WAIT_SECONDS: u64 = 60000;
 
-// Known transparent address from default seed "abandon abandon abandon..."
-const DEFAULT_FAUCET_ADDRESS: &str = "tmBsTi2xWTjUdEXnuTceL7fecEQKeWaPDJd";
+/// How long the faucet wallet gets to catch up with the chain after startup.
+const WALLET_SYNC_TIMEOUT: Duration = Duration::from_secs(180);
 
-pub async fn execute(backend: String, fresh: bool) -> Result<()> {
+/// How long a shield gets to be mined and seen by the wallet.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub async fn execute(
+    backend: String,
+    fresh: bool,
+    seed: Option<String>,
+    seed_file: Option<PathBuf>,
+    activation: ActivationHeights,
+) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!("{}", "  ZecKit - Starting Devnet".cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
     println!();
     
-    let compose = DockerCompose::new()?;
+    // ========================================================================
+    // Choose the faucet seed and derive the address Zebra should mine to
+    // ========================================================================
+    let project_dir = crate::utils::project_dir()?;
+    let (seed_phrase, seed_source) = seed::resolve(&project_dir, seed, seed_file, std::env::var(seed::SEED_ENV).ok())?;
+    let faucet_address = seed::derive_transparent_address(&seed_phrase)?;
+    println!("🔑 Faucet seed: {}", seed_source);
+    
+    // The same heights go to Zebra (via its config) and to the indexers and
+    // faucet (via compose environment), so every component agrees
+    let heights_changed = activation.write_zebra_configs(&project_dir)?;
+    println!("⛓  Activation heights: {}", activation.describe());
+    if heights_changed && !fresh {
+        println!("{}", "⚠ Activation heights changed; an existing chain won't match them. Use --fresh to start over.".yellow());
+    }
+    
+    let compose = activation
+        .env()
+        .into_iter()
+        .fold(DockerCompose::new()?.with_env(seed::SEED_ENV, &seed_phrase), |compose, (key, value)| {
+            compose.with_env(key, &value)
+        });
     
     if fresh {
         println!("{}", "🧹 Cleaning up old data (fresh start)...".yellow());
@@ -30,10 +65,11 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     let services = match backend.as_str() {
         "lwd" => vec!["zebra", "faucet"],
         "zaino" => vec!["zebra", "faucet"],
+        "dual" => vec!["zebra", "zaino", "lightwalletd-dual", "faucet"],
         "none" => vec!["zebra", "faucet"],
         _ => {
             return Err(ZecKitError::Config(format!(
-                "Invalid backend: {}. Use 'lwd', 'zaino', or 'none'", 
+                "Invalid backend: {}. Use 'lwd', 'zaino', 'dual', or 'none'", 
                 backend
             )));
         }
@@ -43,20 +79,20 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     println!();
     
     // ========================================================================
-    // STEP 1: Pre-configure zebra.toml BEFORE starting any containers
+    // STEP 1: Pre-configure the Zebra configs BEFORE starting any containers
     // ========================================================================
     println!("📝 Configuring Zebra mining address...");
     
-    match update_zebra_config_file(DEFAULT_FAUCET_ADDRESS) {
-        Ok(_) => {
-            println!("✓ Updated docker/configs/zebra.toml");
-            println!("  Mining to: {}", DEFAULT_FAUCET_ADDRESS);
-        }
-        Err(e) => {
-            println!("{}", format!("Warning: Could not update zebra.toml: {}", e).yellow());
-            println!("  Using existing config");
+    for relative in ZEBRA_CONFIGS {
+        match update_zebra_config_file(&project_dir.join(relative), &faucet_address) {
+            Ok(_) => println!("✓ Updated {}", relative),
+            Err(e) => {
+                println!("{}", format!("Warning: Could not update {}: {}", relative, e).yellow());
+                println!("  Using existing config");
+            }
         }
     }
+    println!("  Mining to: {}", faucet_address);
     println!();
     
     // ========================================================================
@@ -68,6 +104,10 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     } else if backend == "zaino" {
         compose.up_with_profile("zaino", fresh)?;
         println!();
+    } else if backend == "dual" {
+        // The profile's lightwalletd-dual service publishes on SIDE_LWD_PORT
+        compose.up_with_profile("dual", fresh)?;
+        println!();
     } else {
         compose.up(&services)?;
     }
@@ -113,27 +153,20 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     // ========================================================================
     if backend == "lwd" || backend == "zaino" {
         let backend_name = if backend == "lwd" { "Lightwalletd" } else { "Zaino" };
-        let start = std::time::Instant::now();
         
-        loop {
-            pb.tick();
-            
-            if checker.wait_for_backend(&backend, &pb).await.is_ok() {
-                println!("[2/3] {} ready (100%)", backend_name);
-                break;
-            }
-            
-            let elapsed = start.elapsed().as_secs();
-            if elapsed < 180 {
-                let progress = (elapsed as f64 / 180.0 * 100.0).min(99.0) as u32;
-                print!("\r[2/3] Starting {}... {}%", backend_name, progress);
-                io::stdout().flush().ok();
-                sleep(Duration::from_secs(1)).await;
-            } else {
-                return Err(ZecKitError::ServiceNotReady(format!("{} not ready", backend_name)));
-            }
-        }
-        println!();
+        // Ready means serving gRPC and caught up with Zebra's tip; the
+        // spinner shows the lag meanwhile
+        pb.set_message(format!("[2/3] Waiting for {} gRPC...", backend_name));
+        checker.wait_for_backend(&backend, &pb).await?;
+        pb.set_message("");
+        println!("[2/3] {} ready and caught up with Zebra (100%)", backend_name);
+    } else if backend == "dual" {
+        pb.set_message("[2/3] Waiting for Zaino gRPC...");
+        checker.wait_for_indexer("Zaino", INDEXER_URL, &pb).await?;
+        pb.set_message("[2/3] Waiting for Lightwalletd gRPC...");
+        checker.wait_for_indexer("Lightwalletd", SIDE_LWD_URL, &pb).await?;
+        pb.set_message("");
+        println!("[2/3] Zaino and Lightwalletd ready and caught up with Zebra (100%)");
     }
     
     // ========================================================================
@@ -171,9 +204,9 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     match get_wallet_transparent_address_from_faucet().await {
         Ok(addr) => {
             println!("✓ Faucet wallet address: {}", addr);
-            if addr != DEFAULT_FAUCET_ADDRESS {
+            if addr != faucet_address {
                 println!("{}", "⚠ Warning: Address mismatch!".to_string().yellow());
-                println!("{}", format!("  Expected: {}", DEFAULT_FAUCET_ADDRESS).yellow());
+                println!("{}", format!("  Expected: {}", faucet_address).yellow());
                 println!("{}", format!("  Got:      {}", addr).yellow());
                 println!("{}", "  This may cause funds to be lost!".yellow());
             } else {
@@ -199,14 +232,7 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     mine_additional_blocks(100).await?;
     
     // ========================================================================
-    // STEP 9: Wait for blocks to propagate
-    // ========================================================================
-    println!();
-    println!("Waiting for blocks to propagate...");
-    sleep(Duration::from_secs(10)).await;
-    
-    // ========================================================================
-    // STEP 10: Generate UA fixtures from faucet API
+    // STEP 9: Generate UA fixtures from faucet API
     // ========================================================================
     println!();
     println!("Generating ZIP-316 Unified Address fixtures...");
@@ -221,34 +247,19 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     }
     
     // ========================================================================
-    // STEP 11: Sync wallet through faucet API
+    // STEP 10: Wait for the wallet to sync up to the chain tip
     // ========================================================================
     println!();
     println!("Syncing wallet with blockchain...");
     
-    // Give wallet time to catch up with mined blocks
-    sleep(Duration::from_secs(5)).await;
-    
-    if let Err(e) = sync_wallet_via_faucet().await {
-        println!("{}", format!("Wallet sync warning: {}", e).yellow());
-        println!("  Will retry after waiting...");
-        sleep(Duration::from_secs(10)).await;
-        
-        // Retry once
-        if let Err(e) = sync_wallet_via_faucet().await {
-            println!("{}", format!("Wallet sync still failing: {}", e).yellow());
-        } else {
-            println!("✓ Wallet synced on retry");
-        }
-    } else {
-        println!("✓ Wallet synced with blockchain");
+    let client = Client::new();
+    match Wait::new(&client, WALLET_SYNC_TIMEOUT).for_wallet_at_tip().await {
+        Ok(height) => println!("✓ Wallet synced to height {}", height),
+        Err(e) => println!("{}", format!("Wallet sync warning: {}", e).yellow()),
     }
     
-    // Wait for sync to complete
-    sleep(Duration::from_secs(5)).await;
-    
     // ========================================================================
-    // STEP 12: Check balance BEFORE shielding
+    // STEP 11: Check balance BEFORE shielding
     // ========================================================================
     println!();
     println!("Checking transparent balance...");
@@ -274,27 +285,22 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
     }
     
     // ========================================================================
-    // STEP 13: Shield transparent funds to orchard
+    // STEP 12: Shield transparent funds to orchard
     // ========================================================================
     println!();
-    if let Err(e) = shield_transparent_funds().await {
-        println!("{}", format!("Shield operation: {}", e).yellow());
-    } else {
-        // Sync again after shielding
-        println!("Re-syncing after shielding...");
-        sleep(Duration::from_secs(15)).await;
-        
-        if let Err(e) = sync_wallet_via_faucet().await {
-            println!("{}", format!("Warning: Post-shield sync failed: {}", e).yellow());
-        } else {
-            println!("✓ Post-shield sync complete");
+    match shield_transparent_funds().await {
+        Ok(txid) => {
+            println!("  Waiting for confirmation...");
+            match Wait::new(&client, CONFIRMATION_TIMEOUT).mining().for_confirmed(&txid).await {
+                Ok(height) => println!("✓ Shield mined at height {} and synced", height),
+                Err(e) => println!("{}", format!("Warning: {}", e).yellow()),
+            }
         }
-        
-        sleep(Duration::from_secs(5)).await;
+        Err(e) => println!("{}", format!("Shield operation: {}", e).yellow()),
     }
     
     // ========================================================================
-    // STEP 14: Final balance check
+    // STEP 13: Final balance check
     // ========================================================================
     println!();
     println!("Final wallet balance:");
@@ -314,6 +320,15 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
         }
     }
     
+    // ========================================================================
+    // STEP 14: Report faucet readiness (indexer, sync, balance, last sync)
+    // ========================================================================
+    println!();
+    match checker.faucet_readiness().await {
+        Ok((_, report)) => crate::commands::status::print_readiness(&report),
+        Err(e) => println!("{}", format!("Could not check faucet readiness: {}", e).yellow()),
+    }
+    
     // ========================================================================
     // STEP 15: Start background miner
     // ========================================================================
@@ -333,23 +348,13 @@ pub async fn execute(backend: String, fresh: bool) -> Result<()> {
 }
 
 // ============================================================================
-// NEW FUNCTION: Update zebra.toml on host before starting containers
+// NEW FUNCTION: Update the Zebra configs on host before starting containers
 // ============================================================================
-fn update_zebra_config_file(address: &str) -> Result<()> {
+fn update_zebra_config_file(config_path: &Path, address: &str) -> Result<()> {
     use regex::Regex;
     
-    // Get project root (same logic as DockerCompose::new())
-    let current_dir = std::env::current_dir()?;
-    let project_dir = if current_dir.ends_with("cli") {
-        current_dir.parent().unwrap().to_path_buf()
-    } else {
-        current_dir.clone()
-    };
-    
-    let config_path = project_dir.join("docker/configs/zebra.toml");
-    
     // Read current config
-    let config = fs::read_to_string(&config_path)
+    let config = fs::read_to_string(config_path)
         .map_err(|e| ZecKitError::Config(format!("Could not read {:?}: {}", config_path, e)))?;
     
     // Update miner address using regex
@@ -372,7 +377,7 @@ fn update_zebra_config_file(address: &str) -> Result<()> {
     };
     
     // Write back to file
-    fs::write(&config_path, updated)
+    fs::write(config_path, updated)
         .map_err(|e| ZecKitError::Config(format!("Could not write {:?}: {}", config_path, e)))?;
     
     Ok(())
@@ -466,7 +471,8 @@ async fn start_background_miner() -> Result<()> {
     Ok(())
 }
 
-async fn shield_transparent_funds() -> Result<()> {
+/// Shield the wallet's transparent funds; returns the shield txid.
+async fn shield_transparent_funds() -> Result<String> {
     let client = Client::new();
     
     println!("Shielding transparent funds to Orchard...");
@@ -486,9 +492,7 @@ async fn shield_transparent_funds() -> Result<()> {
     if let Some(txid) = json.get("txid").and_then(|v| v.as_str()) {
         println!("✓ Shielded {} ZEC", json["transparent_amount"].as_f64().unwrap_or(0.0));
         println!("  Transaction ID: {}", txid);
-        println!("  Waiting for confirmation...");
-        sleep(Duration::from_secs(20)).await;
-        return Ok(());
+        return Ok(txid.to_string());
     }
     
     Err(ZecKitError::HealthCheck("Shield transaction failed".into()))
@@ -563,27 +567,6 @@ async fn generate_ua_fixtures_from_faucet() -> Result<String> {
     Ok(ua_address.to_string())
 }
 
-async fn sync_wallet_via_faucet() -> Result<()> {
-    let client = Client::new();
-    
-    let resp = client
-        .post("http://127.0.0.1:8080/sync")
-        .timeout(Duration::from_secs(60))
-        .send()
-        .await
-        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet sync failed: {}", e)))?;
-    
-    if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        return Err(ZecKitError::HealthCheck(
-            format!("Wallet sync failed ({}): {}", status, body)
-        ));
-    }
-    
-    Ok(())
-}
-
 async fn check_wallet_balance() -> Result<(f64, f64, f64)> {
     let client = Client::new();
     let resp = client
@@ -631,6 +614,10 @@ fn print_connection_info(backend: &str) {
         println!("  LightwalletD: http://127.0.0.1:9067");
     } else if backend == "zaino" {
         println!("  Zaino: http://127.0.0.1:9067");
+    } else if backend == "dual" {
+        println!("  Zaino: {}", INDEXER_URL);
+        println!("  LightwalletD: {}", SIDE_LWD_URL);
+        println!("  Switch faucet backend: curl -X POST http://127.0.0.1:8080/admin/backend -H 'Content-Type: application/json' -d '{{\"backend\":\"lightwalletd\"}}'");
     }
     
     println!();
@@ -639,4 +626,22 @@ fn print_connection_info(backend: &str) {
     println!("  • View fixtures: cat fixtures/unified-addresses.json");
     println!("  • Request funds: curl -X POST http://127.0.0.1:8080/request -d '{{\"address\":\"...\"}}'");
     println!();
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_miner_address() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zebra.toml");
+        fs::write(&path, "[mining]\nminer_address = \"tmOld\"\n\n[rpc]\nlisten_addr = \"0.0.0.0:8232\"\n").unwrap();
+
+        update_zebra_config_file(&path, "tmNew").unwrap();
+
+        let config = fs::read_to_string(&path).unwrap();
+        assert!(config.contains("miner_address = \"tmNew\""));
+        assert!(!config.contains("tmOld"));
+        assert!(config.contains("listen_addr = \"0.0.0.0:8232\""));
+    }
+}
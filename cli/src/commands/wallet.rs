@@ -0,0 +1,159 @@
+use crate::error::{Result, ZecKitError};
+use colored::*;
+use reqwest::Client;
+use serde_json::Value;
+use std::io::{self, Write};
+use tokio::time::{sleep, Duration, Instant};
+
+const RESCAN_TIMEOUT_SECS: u64 = 600;
+
+/// Rebuild the faucet wallet from its seed and rescan, without touching Zebra.
+pub async fn rescan(from_height: Option<u32>, reset: bool, wait: bool) -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!("{}", "  ZecKit - Wallet Rescan".cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".cyan());
+    println!();
+
+    let client = Client::new();
+
+    let request = if reset {
+        println!("Resetting faucet wallet and history...");
+        client.post("http://127.0.0.1:8080/admin/reset-wallet")
+    } else {
+        let from_height = from_height.unwrap_or(0);
+        println!("Rebuilding faucet wallet from seed (rescan from height {})...", from_height);
+        client
+            .post("http://127.0.0.1:8080/admin/rescan")
+            .query(&[("from_height", from_height)])
+    };
+
+    let resp = request
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| ZecKitError::HealthCheck(format!("Faucet API call failed: {}", e)))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(ZecKitError::HealthCheck(
+            format!("Rescan request failed ({}): {}", status, body)
+        ));
+    }
+
+    let json: Value = resp.json().await?;
+    if let Some(message) = json["message"].as_str() {
+        println!("✓ {}", message);
+    }
+
+    if !wait {
+        println!();
+        println!("Follow progress with: curl http://127.0.0.1:8080/sync/status");
+        return Ok(());
+    }
+
+    let rescan_id = json["rescan_id"].as_u64().ok_or_else(|| {
+        ZecKitError::HealthCheck("Faucet did not return a rescan_id; is it up to date?".into())
+    })?;
+
+    println!();
+    wait_for_rescan(&client, rescan_id).await?;
+
+    println!();
+    println!("{}", "✓ Wallet rescan complete".green().bold());
+    println!();
+
+    Ok(())
+}
+
+/// Poll `/sync/status` until rescan `rescan_id` finishes. Background syncs
+/// in between don't count; a later rescan superseding it does.
+async fn wait_for_rescan(client: &Client, rescan_id: u64) -> Result<()> {
+    let start = Instant::now();
+
+    loop {
+        if let Ok(status) = get_sync_status(client).await {
+            match rescan_outcome(&status, rescan_id) {
+                Some(Ok(())) => {
+                    println!(
+                        "\r  Wallet height {} / {}          ",
+                        status["wallet_height"],
+                        status["chain_height"]
+                    );
+                    return Ok(());
+                }
+                Some(Err(error)) => {
+                    println!();
+                    return Err(ZecKitError::HealthCheck(format!("Rescan failed: {}", error)));
+                }
+                None => {}
+            }
+
+            print!(
+                "\r  Rescanning... wallet height {} / {} ({:.0}%)",
+                status["wallet_height"],
+                status["chain_height"],
+                status["percent"].as_f64().unwrap_or(0.0)
+            );
+            io::stdout().flush().ok();
+        }
+
+        if start.elapsed().as_secs() > RESCAN_TIMEOUT_SECS {
+            println!();
+            return Err(ZecKitError::ServiceNotReady(
+                format!("Wallet rescan did not finish within {}s", RESCAN_TIMEOUT_SECS)
+            ));
+        }
+
+        sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// How rescan `rescan_id` ended according to a `/sync/status` response, or
+/// `None` while it is still running.
+fn rescan_outcome(status: &Value, rescan_id: u64) -> Option<std::result::Result<(), String>> {
+    let rescan = &status["rescan"];
+    if rescan["id"].as_u64()? < rescan_id || rescan["finished_at"].is_null() {
+        return None;
+    }
+
+    match rescan["error"].as_str() {
+        Some(error) => Some(Err(error.to_string())),
+        None => Some(Ok(())),
+    }
+}
+
+async fn get_sync_status(client: &Client) -> Result<Value> {
+    let resp = client
+        .get("http://127.0.0.1:8080/sync/status")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    Ok(resp.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn waits_for_its_own_rescan() {
+        // A background sync finishing doesn't end the wait
+        let syncing = json!({ "running": false, "sync_count": 7, "rescan": null });
+        assert_eq!(rescan_outcome(&syncing, 3), None);
+
+        let earlier = json!({ "rescan": { "id": 2, "finished_at": "2026-01-01T00:00:00Z", "error": null } });
+        assert_eq!(rescan_outcome(&earlier, 3), None);
+
+        let running = json!({ "rescan": { "id": 3, "finished_at": null, "error": null } });
+        assert_eq!(rescan_outcome(&running, 3), None);
+
+        let done = json!({ "rescan": { "id": 3, "finished_at": "2026-01-01T00:00:00Z", "error": null } });
+        assert_eq!(rescan_outcome(&done, 3), Some(Ok(())));
+
+        let failed = json!({ "rescan": { "id": 4, "finished_at": "2026-01-01T00:00:00Z", "error": "timed out" } });
+        assert_eq!(rescan_outcome(&failed, 3), Some(Err("timed out".to_string())));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::error::{Result, ZecKitError};
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Zebra configs that carry the regtest activation heights.
+pub const ZEBRA_CONFIGS: [&str; 2] = ["docker/configs/zebra.toml", "docker/configs/zebra-fork.toml"];
+
+const ZEBRA_SECTION: &str = "[network.testnet_parameters.activation_heights]";
+
+/// Regtest network upgrade activation heights. Upgrades up to Canopy always
+/// activate at height 1; an upgrade without a height never activates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivationHeights {
+    pub nu5: u32,
+    pub nu6: Option<u32>,
+    pub nu6_1: Option<u32>,
+    pub nu7: Option<u32>,
+}
+
+impl Default for ActivationHeights {
+    fn default() -> Self {
+        Self { nu5: 1, nu6: None, nu6_1: None, nu7: None }
+    }
+}
+
+/// Parses `--activation nu6=150,nu6_1=300`. Upgrades left out keep their
+/// default: NU5 at 1, the rest inactive.
+impl FromStr for ActivationHeights {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut heights = Self::default();
+
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, height) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected UPGRADE=HEIGHT, got {:?}", pair))?;
+            let height: u32 = height
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid height for {}: {:?}", name, height))?;
+
+            match name.trim().to_lowercase().as_str() {
+                "nu5" => heights.nu5 = height,
+                "nu6" => heights.nu6 = Some(height),
+                "nu6_1" | "nu6.1" => heights.nu6_1 = Some(height),
+                "nu7" => heights.nu7 = Some(height),
+                other => {
+                    return Err(format!("unknown network upgrade {:?} (expected nu5, nu6, nu6_1 or nu7)", other))
+                }
+            }
+        }
+
+        heights.validate()?;
+        Ok(heights)
+    }
+}
+
+impl ActivationHeights {
+    /// Upgrades activate in order, each no earlier than the one before.
+    fn validate(&self) -> std::result::Result<(), String> {
+        if self.nu5 == 0 {
+            return Err("nu5 must activate at height 1 or later".to_string());
+        }
+
+        let mut previous = ("nu5", Some(self.nu5));
+        for (name, height) in [("nu6", self.nu6), ("nu6_1", self.nu6_1), ("nu7", self.nu7)] {
+            if let Some(height) = height {
+                match previous.1 {
+                    None => return Err(format!("{} needs {} to activate first", name, previous.0)),
+                    Some(before) if height < before => {
+                        return Err(format!("{} ({}) cannot activate before {} ({})", name, height, previous.0, before))
+                    }
+                    _ => {}
+                }
+            }
+            previous = (name, height);
+        }
+
+        Ok(())
+    }
+
+    /// Environment for docker-compose interpolation; an empty value means
+    /// the upgrade never activates.
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let value = |h: Option<u32>| h.map(|h| h.to_string()).unwrap_or_default();
+        vec![
+            ("ZECKIT_NU5", self.nu5.to_string()),
+            ("ZECKIT_NU6", value(self.nu6)),
+            ("ZECKIT_NU6_1", value(self.nu6_1)),
+            ("ZECKIT_NU7", value(self.nu7)),
+        ]
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = vec![format!("NU5={}", self.nu5)];
+        for (name, height) in [("NU6", self.nu6), ("NU6.1", self.nu6_1), ("NU7", self.nu7)] {
+            if let Some(height) = height {
+                parts.push(format!("{}={}", name, height));
+            }
+        }
+        parts.join(", ")
+    }
+
+    /// Zebra's activation heights table, using Zebra's upgrade names.
+    fn zebra_section(&self) -> String {
+        let mut section = format!("{}\nNU5 = {}\n", ZEBRA_SECTION, self.nu5);
+        for (name, height) in [("NU6", self.nu6), ("\"NU6.1\"", self.nu6_1), ("NU7", self.nu7)] {
+            if let Some(height) = height {
+                section.push_str(&format!("{} = {}\n", name, height));
+            }
+        }
+        section
+    }
+
+    /// Replace the activation heights table in a Zebra config, adding it at
+    /// the end if there is none. The table must stay the last one in the file.
+    fn apply_to_zebra(&self, config: &str) -> String {
+        let kept = match config.find(ZEBRA_SECTION) {
+            Some(start) => &config[..start],
+            None => config,
+        };
+        format!("{}\n\n{}", kept.trim_end(), self.zebra_section())
+    }
+
+    /// Write the heights into every Zebra config. Returns true when the main
+    /// node's heights changed, which invalidates an existing chain.
+    pub fn write_zebra_configs(&self, project_dir: &Path) -> Result<bool> {
+        let mut changed = false;
+
+        for (i, relative) in ZEBRA_CONFIGS.iter().enumerate() {
+            let path = project_dir.join(relative);
+            let config = fs::read_to_string(&path)
+                .map_err(|e| ZecKitError::Config(format!("Could not read {:?}: {}", path, e)))?;
+
+            if i == 0 {
+                changed = Self::from_zebra(&config) != *self;
+            }
+
+            fs::write(&path, self.apply_to_zebra(&config))
+                .map_err(|e| ZecKitError::Config(format!("Could not write {:?}: {}", path, e)))?;
+        }
+
+        Ok(changed)
+    }
+
+    /// Heights the main Zebra node is configured with.
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(ZEBRA_CONFIGS[0]);
+        let config = fs::read_to_string(&path)
+            .map_err(|e| ZecKitError::Config(format!("Could not read {:?}: {}", path, e)))?;
+        Ok(Self::from_zebra(&config))
+    }
+
+    /// Heights currently in a Zebra config.
+    fn from_zebra(config: &str) -> Self {
+        let section = match config.find(ZEBRA_SECTION) {
+            Some(start) => &config[start..],
+            None => return Self::default(),
+        };
+        let height = |name: &str| {
+            Regex::new(&format!(r#"(?m)^{}\s*=\s*(\d+)"#, regex::escape(name)))
+                .ok()
+                .and_then(|re| re.captures(section))
+                .and_then(|c| c[1].parse().ok())
+        };
+
+        Self {
+            nu5: height("NU5").unwrap_or(1),
+            nu6: height("NU6"),
+            nu6_1: height("\"NU6.1\""),
+            nu7: height("NU7"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_validates_heights() {
+        let heights: ActivationHeights = "nu6=150,nu6_1=300".parse().unwrap();
+        assert_eq!(heights, ActivationHeights { nu5: 1, nu6: Some(150), nu6_1: Some(300), nu7: None });
+
+        assert!("nu6_1=300".parse::<ActivationHeights>().is_err());
+        assert!("nu6=300,nu6.1=150".parse::<ActivationHeights>().is_err());
+        assert!("nu8=10".parse::<ActivationHeights>().is_err());
+    }
+
+    #[test]
+    fn rewrites_zebra_section() {
+        let config = "[mining]\ninternal_miner = true\n\n[network.testnet_parameters.activation_heights]\nNU5 = 1";
+        let heights: ActivationHeights = "nu6=150,nu6.1=300".parse().unwrap();
+
+        let updated = heights.apply_to_zebra(config);
+        assert!(updated.starts_with("[mining]\ninternal_miner = true\n\n"));
+        assert!(updated.ends_with("NU5 = 1\nNU6 = 150\n\"NU6.1\" = 300\n"));
+        assert_eq!(ActivationHeights::from_zebra(&updated), heights);
+        assert_eq!(ActivationHeights::from_zebra(config), ActivationHeights::default());
+    }
+}
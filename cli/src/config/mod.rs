@@ -1 +1,3 @@
-pub mod settings;
\ No newline at end of file
+pub mod settings;
+pub mod seed;
+pub mod activation;
\ No newline at end of file
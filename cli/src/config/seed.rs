@@ -0,0 +1,158 @@
+use crate::error::{Result, ZecKitError};
+use bip32::{DerivationPath, XPrv};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Public regtest mnemonic used when no seed is configured. Anyone can spend
+/// from it, which is fine on regtest and keeps the devnet reproducible.
+pub const DEFAULT_SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
+/// Where `zeckit up` remembers the chosen seed between runs (relative to the project root).
+pub const SEED_FILE: &str = "docker/configs/faucet.seed";
+
+/// Environment variable read by `zeckit up` and forwarded to the faucet container.
+pub const SEED_ENV: &str = "ZECKIT_FAUCET_SEED";
+
+/// Regtest transparent P2PKH version bytes ("tm..." addresses).
+const REGTEST_P2PKH_PREFIX: [u8; 2] = [0x1d, 0x25];
+
+/// BIP-44 path of the first external transparent address of account 0
+/// (coin type 1 is shared by testnet and regtest).
+const TRANSPARENT_PATH: &str = "m/44'/1'/0'/0/0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedSource {
+    Flag,
+    Env,
+    File,
+    Saved,
+    Generated,
+    Default,
+}
+
+impl std::fmt::Display for SeedSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            SeedSource::Flag => "--seed",
+            SeedSource::Env => SEED_ENV,
+            SeedSource::File => "--seed-file",
+            SeedSource::Saved => SEED_FILE,
+            SeedSource::Generated => "freshly generated",
+            SeedSource::Default => "default regtest seed",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Pick the faucet seed for this run.
+///
+/// Precedence: `--seed`, then `--seed-file`, then `ZECKIT_FAUCET_SEED`, then
+/// the seed saved by a previous `up`, then the public default. Flags beat the
+/// environment, so a seed exported in the shell can't override either one. The value
+/// `random` generates a new 24-word mnemonic. The chosen seed is saved so
+/// restarts keep mining to the same wallet. `env_seed` is the value of
+/// `ZECKIT_FAUCET_SEED`, passed in by the caller.
+pub fn resolve(
+    project_dir: &Path,
+    seed_arg: Option<String>,
+    seed_file: Option<PathBuf>,
+    env_seed: Option<String>,
+) -> Result<(String, SeedSource)> {
+    let saved_path = project_dir.join(SEED_FILE);
+
+    let (phrase, source) = if let Some(seed) = seed_arg {
+        (seed, SeedSource::Flag)
+    } else if let Some(path) = seed_file {
+        let seed = fs::read_to_string(&path)
+            .map_err(|e| ZecKitError::Config(format!("Could not read seed file {:?}: {}", path, e)))?;
+        (seed, SeedSource::File)
+    } else if let Some(seed) = env_seed.filter(|s| !s.trim().is_empty()) {
+        (seed, SeedSource::Env)
+    } else if let Ok(seed) = fs::read_to_string(&saved_path) {
+        (seed, SeedSource::Saved)
+    } else {
+        (DEFAULT_SEED.to_string(), SeedSource::Default)
+    };
+
+    let (phrase, source) = if phrase.trim() == "random" {
+        (generate_mnemonic(), SeedSource::Generated)
+    } else {
+        (normalize(&phrase), source)
+    };
+
+    bip39::Mnemonic::parse_normalized(&phrase)
+        .map_err(|e| ZecKitError::Config(format!("Invalid faucet seed ({}): {}", source, e)))?;
+
+    if source != SeedSource::Saved {
+        fs::write(&saved_path, &phrase)
+            .map_err(|e| ZecKitError::Config(format!("Could not write {:?}: {}", saved_path, e)))?;
+    }
+
+    Ok((phrase, source))
+}
+
+/// Generate a new random 24-word BIP-39 mnemonic.
+pub fn generate_mnemonic() -> String {
+    bip39::Mnemonic::generate(24)
+        .expect("24 is a valid BIP-39 word count")
+        .to_string()
+}
+
+/// Derive the faucet's regtest transparent address (`tm...`) from its
+/// mnemonic, matching the first transparent address zingolib reports.
+pub fn derive_transparent_address(phrase: &str) -> Result<String> {
+    let mnemonic = bip39::Mnemonic::parse_normalized(phrase)
+        .map_err(|e| ZecKitError::Config(format!("Invalid mnemonic: {}", e)))?;
+    let seed = mnemonic.to_seed("");
+
+    let path: DerivationPath = TRANSPARENT_PATH
+        .parse()
+        .map_err(|e| ZecKitError::Config(format!("Invalid derivation path: {}", e)))?;
+    let xprv = XPrv::derive_from_path(seed, &path)
+        .map_err(|e| ZecKitError::Config(format!("Key derivation failed: {}", e)))?;
+
+    let public_key = xprv.public_key().to_bytes();
+    let pubkey_hash = Ripemd160::digest(Sha256::digest(public_key));
+
+    let mut payload = REGTEST_P2PKH_PREFIX.to_vec();
+    payload.extend_from_slice(&pubkey_hash);
+
+    Ok(bs58::encode(payload).with_check().into_string())
+}
+
+fn normalize(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_default_seed_address() {
+        assert_eq!(
+            derive_transparent_address(DEFAULT_SEED).unwrap(),
+            "tmBsTi2xWTjUdEXnuTceL7fecEQKeWaPDJd"
+        );
+    }
+
+    #[test]
+    fn seed_file_beats_environment() {
+        let project = tempfile::tempdir().unwrap();
+        fs::create_dir_all(project.path().join("docker/configs")).unwrap();
+        let seed_file = project.path().join("my-seed.txt");
+        fs::write(&seed_file, format!("  {}\n", DEFAULT_SEED)).unwrap();
+
+        let (phrase, source) = resolve(project.path(), None, Some(seed_file), Some(generate_mnemonic())).unwrap();
+        assert_eq!(source, SeedSource::File);
+        assert_eq!(phrase, DEFAULT_SEED);
+        assert_eq!(fs::read_to_string(project.path().join(SEED_FILE)).unwrap(), DEFAULT_SEED);
+
+        let env_seed = generate_mnemonic();
+        let (phrase, source) = resolve(project.path(), None, None, Some(env_seed.clone())).unwrap();
+        assert_eq!(source, SeedSource::Env);
+        assert_eq!(phrase, env_seed);
+    }
+}
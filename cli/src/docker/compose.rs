@@ -4,28 +4,32 @@ use std::process::{Command, Stdio};
 #[derive(Clone)]
 pub struct DockerCompose {
     project_dir: String,
+    env: Vec<(String, String)>,
 }
 
 impl DockerCompose {
     pub fn new() -> Result<Self> {
-        // Get project root (go up from cli/ directory)
-        let current_dir = std::env::current_dir()?;
-        let project_dir = if current_dir.ends_with("cli") {
-            current_dir.parent().unwrap().to_path_buf()
-        } else {
-            current_dir
-        };
+        let project_dir = crate::utils::project_dir()?;
 
         Ok(Self {
             project_dir: project_dir.to_string_lossy().to_string(),
+            env: Vec::new(),
         })
     }
 
+    /// Pass an environment variable to `docker compose up`, for use in
+    /// `${VAR}` interpolation in docker-compose.yml.
+    pub fn with_env(mut self, key: &str, value: &str) -> Self {
+        self.env.push((key.to_string(), value.to_string()));
+        self
+    }
+
     pub fn up(&self, services: &[&str]) -> Result<()> {
         let mut cmd = Command::new("docker");
         cmd.arg("compose")
             .arg("up")
             .arg("-d")
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
             .current_dir(&self.project_dir);
 
         for service in services {
@@ -42,6 +46,68 @@ impl DockerCompose {
         Ok(())
     }
 
+    /// Start a service from a new container, discarding its previous state.
+    pub fn recreate(&self, service: &str) -> Result<()> {
+        let output = Command::new("docker")
+            .arg("compose")
+            .arg("up")
+            .arg("-d")
+            .arg("--force-recreate")
+            .arg(service)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .current_dir(&self.project_dir)
+            .output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(ZecKitError::Docker(error.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Stop and remove a single service's container.
+    pub fn remove(&self, service: &str) -> Result<()> {
+        let output = Command::new("docker")
+            .arg("compose")
+            .arg("rm")
+            .arg("--stop")
+            .arg("--force")
+            .arg(service)
+            .current_dir(&self.project_dir)
+            .output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(ZecKitError::Docker(error.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Names of the services with a running container.
+    pub fn running_services(&self) -> Result<Vec<String>> {
+        let output = Command::new("docker")
+            .arg("compose")
+            .arg("ps")
+            .arg("--services")
+            .arg("--filter")
+            .arg("status=running")
+            .current_dir(&self.project_dir)
+            .output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(ZecKitError::Docker(error.to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+
     /// Check if Docker images exist for a profile
     pub fn images_exist(&self, profile: &str) -> bool {
         // Get list of images that would be used by this profile
@@ -114,6 +180,7 @@ impl DockerCompose {
             .arg(profile)
             .arg("up")
             .arg("-d")
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
             .current_dir(&self.project_dir)
             .status()?
             .success()
@@ -1,10 +1,25 @@
 use crate::error::{Result, ZecKitError};
+use crate::indexer::{IndexerClient, INDEXER_URL};
 use reqwest::Client;
 use indicatif::ProgressBar;
 use tokio::time::{sleep, Duration};
 use serde_json::Value;
-use std::net::TcpStream;
-use std::time::Duration as StdDuration;
+
+/// The indexer counts as caught up within this many blocks of Zebra's tip;
+/// the background miner keeps moving the tip while we compare.
+const MAX_INDEXER_LAG: u64 = 1;
+
+/// Heights seen by one backend readiness check.
+pub struct IndexerProgress {
+    pub indexer_height: u64,
+    pub chain_height: u64,
+}
+
+impl IndexerProgress {
+    pub fn lag(&self) -> u64 {
+        self.chain_height.saturating_sub(self.indexer_height)
+    }
+}
 
 pub struct HealthChecker {
     client: Client,
@@ -55,20 +70,40 @@ impl HealthChecker {
         Err(ZecKitError::ServiceNotReady("Faucet".into()))
     }
 
+    /// Wait until the indexer answers gRPC and has caught up with Zebra,
+    /// showing how far behind it is on the spinner.
     pub async fn wait_for_backend(&self, backend: &str, pb: &ProgressBar) -> Result<()> {
-        for i in 0..self.backend_max_retries {
+        let backend_name = if backend == "lwd" { "Lightwalletd" } else { "Zaino" };
+        self.wait_for_indexer(backend_name, INDEXER_URL, pb).await
+    }
+
+    /// Like [`Self::wait_for_backend`], for an indexer at any gRPC URL.
+    pub async fn wait_for_indexer(&self, name: &str, url: &str, pb: &ProgressBar) -> Result<()> {
+        let mut last_error = None;
+
+        for _ in 0..self.backend_max_retries {
             pb.tick();
             
-            match self.check_backend(backend).await {
-                Ok(_) => return Ok(()),
-                Err(_) if i < self.backend_max_retries - 1 => {
-                    sleep(self.retry_delay).await;
+            match self.check_indexer(url).await {
+                Ok(progress) if progress.lag() <= MAX_INDEXER_LAG => return Ok(()),
+                Ok(progress) => {
+                    pb.set_message(format!(
+                        "{} at block {}, Zebra at {} ({} behind)",
+                        name, progress.indexer_height, progress.chain_height, progress.lag()
+                    ));
+                }
+                Err(e) => {
+                    pb.set_message(format!("Waiting for {} gRPC: {}", name, e));
+                    last_error = Some(e);
                 }
-                Err(e) => return Err(e),
             }
+            sleep(self.retry_delay).await;
         }
 
-        Err(ZecKitError::ServiceNotReady(format!("{} not ready", backend)))
+        Err(ZecKitError::ServiceNotReady(match last_error {
+            Some(e) => format!("{} not ready: {}", name, e),
+            None => format!("{} did not catch up with Zebra", name),
+        }))
     }
 
     async fn check_zebra(&self) -> Result<()> {
@@ -92,10 +127,11 @@ impl HealthChecker {
         }
     }
 
+    /// The faucet process is up; it may still be syncing or unfunded.
     async fn check_faucet(&self) -> Result<()> {
         let resp = self
             .client
-            .get("http://127.0.0.1:8080/health")
+            .get("http://127.0.0.1:8080/livez")
             .timeout(Duration::from_secs(5))
             .send()
             .await?;
@@ -104,37 +140,55 @@ impl HealthChecker {
             return Err(ZecKitError::HealthCheck("Faucet not ready".into()));
         }
 
-        let json: Value = resp.json().await?;
-        
-        if json.get("status").and_then(|s| s.as_str()) == Some("unhealthy") {
-            return Err(ZecKitError::HealthCheck("Faucet unhealthy".into()));
-        }
-
         Ok(())
     }
+
+    /// The faucet's `/readyz` report: whether it can serve funds, and the
+    /// status of each component (indexer, sync, balance, last_sync).
+    pub async fn faucet_readiness(&self) -> Result<(bool, Value)> {
+        let resp = self
+            .client
+            .get("http://127.0.0.1:8080/readyz")
+            .timeout(Duration::from_secs(15))
+            .send()
+            .await?;
+
+        // 503 still carries the component report
+        let ready = resp.status().is_success();
+        let json: Value = resp.json().await?;
+        Ok((ready, json))
+    }
     
-    async fn check_backend(&self, backend: &str) -> Result<()> {
-        // Zaino and Lightwalletd are gRPC services on port 9067
-        // They don't respond to HTTP, so we do a TCP connection check
-        
-        let backend_name = if backend == "lwd" { "lightwalletd" } else { "zaino" };
-        
-        // Try to connect to localhost:9067 with 2 second timeout
-        match TcpStream::connect_timeout(
-            &"127.0.0.1:9067".parse().unwrap(),
-            StdDuration::from_secs(2)
-        ) {
-            Ok(_) => {
-                // For Zaino, give it extra time after port opens to initialize
-                if backend == "zaino" {
-                    sleep(Duration::from_secs(10)).await;
-                }
-                Ok(())
-            }
-            Err(_) => {
-                // Port not accepting connections yet
-                Err(ZecKitError::HealthCheck(format!("{} not ready", backend_name)))
-            }
-        }
+    /// Ask the indexer for its info and latest block over gRPC, and Zebra
+    /// for its tip.
+    async fn check_indexer(&self, url: &str) -> Result<IndexerProgress> {
+        let mut indexer = IndexerClient::connect(url).await?;
+        indexer.get_lightd_info().await?;
+        let latest = indexer.get_latest_block().await?;
+
+        Ok(IndexerProgress {
+            indexer_height: latest.height,
+            chain_height: self.zebra_height().await?,
+        })
     }
-}
\ No newline at end of file
+
+    async fn zebra_height(&self) -> Result<u64> {
+        let resp = self
+            .client
+            .post("http://127.0.0.1:8232")
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "health",
+                "method": "getblockcount",
+                "params": []
+            }))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await?;
+
+        let json: Value = resp.json().await?;
+        json.get("result")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| ZecKitError::HealthCheck("Invalid block count response".into()))
+    }
+}
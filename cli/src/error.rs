@@ -13,6 +13,13 @@ pub enum ZecKitError {
     #[error("Service not ready: {0}")]
     ServiceNotReady(String),
     
+    #[error("Timed out after {secs}s waiting for {what} (last seen: {last_seen})")]
+    WaitTimeout {
+        what: String,
+        secs: u64,
+        last_seen: String,
+    },
+    
     #[error("Configuration error: {0}")]
     Config(String),
     
@@ -24,4 +31,19 @@ pub enum ZecKitError {
     
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    
+    #[error("gRPC error: {0}")]
+    Grpc(String),
+}
+
+impl From<tonic::Status> for ZecKitError {
+    fn from(status: tonic::Status) -> Self {
+        ZecKitError::Grpc(format!("{:?}: {}", status.code(), status.message()))
+    }
+}
+
+impl From<tonic::transport::Error> for ZecKitError {
+    fn from(e: tonic::transport::Error) -> Self {
+        ZecKitError::Grpc(e.to_string())
+    }
 }
@@ -0,0 +1,161 @@
+pub mod proto;
+
+use crate::error::{Result, ZecKitError};
+use proto::{
+    BlockId, BlockRange, ChainSpec, CompactBlock, CompactTx, Empty, Exclude, LightdInfo,
+    RawTransaction, TransparentAddressBlockFilter, TreeState, TxFilter,
+};
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tokio::time::Duration;
+
+/// Host address of the indexer's gRPC port (Zaino or lightwalletd).
+pub const INDEXER_URL: &str = "http://127.0.0.1:9067";
+
+/// Host port lightwalletd moves to when it runs next to Zaino (the `dual`
+/// profile's `lightwalletd-dual` service and `compare-backends`), and the
+/// matching gRPC address.
+pub const SIDE_LWD_PORT: &str = "9077";
+pub const SIDE_LWD_URL: &str = "http://127.0.0.1:9077";
+
+const SERVICE: &str = "/cash.z.wallet.sdk.rpc.CompactTxStreamer";
+
+/// Client for the `CompactTxStreamer` gRPC service that both Zaino and
+/// lightwalletd implement.
+#[derive(Clone)]
+pub struct IndexerClient {
+    grpc: Grpc<Channel>,
+}
+
+impl IndexerClient {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let channel = Endpoint::from_shared(url.to_string())
+            .map_err(|e| ZecKitError::Grpc(format!("Invalid indexer URL {}: {}", url, e)))?
+            .connect_timeout(Duration::from_secs(2))
+            .timeout(Duration::from_secs(30))
+            .connect()
+            .await?;
+
+        Ok(Self { grpc: Grpc::new(channel) })
+    }
+
+    pub async fn get_lightd_info(&mut self) -> Result<LightdInfo> {
+        self.unary("GetLightdInfo", Empty {}).await
+    }
+
+    pub async fn get_latest_block(&mut self) -> Result<BlockId> {
+        self.unary("GetLatestBlock", ChainSpec {}).await
+    }
+
+    pub async fn get_block_range(&mut self, start: u64, end: u64) -> Result<Vec<CompactBlock>> {
+        let range = BlockRange {
+            start: Some(BlockId { height: start, hash: Vec::new() }),
+            end: Some(BlockId { height: end, hash: Vec::new() }),
+        };
+        self.server_streaming("GetBlockRange", range).await
+    }
+
+    pub async fn get_tree_state(&mut self, height: u64) -> Result<TreeState> {
+        self.unary("GetTreeState", BlockId { height, hash: Vec::new() }).await
+    }
+
+    /// `hash` is the txid in the byte order of `CompactTx::hash`.
+    pub async fn get_transaction(&mut self, hash: &[u8]) -> Result<RawTransaction> {
+        let filter = TxFilter { block: None, index: 0, hash: hash.to_vec() };
+        self.unary("GetTransaction", filter).await
+    }
+
+    pub async fn get_taddress_txids(
+        &mut self,
+        address: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<RawTransaction>> {
+        let filter = TransparentAddressBlockFilter {
+            address: address.to_string(),
+            range: Some(BlockRange {
+                start: Some(BlockId { height: start, hash: Vec::new() }),
+                end: Some(BlockId { height: end, hash: Vec::new() }),
+            }),
+        };
+        self.server_streaming("GetTaddressTxids", filter).await
+    }
+
+    pub async fn get_mempool_tx(&mut self) -> Result<Vec<CompactTx>> {
+        self.server_streaming("GetMempoolTx", Exclude { txid: Vec::new() }).await
+    }
+
+    async fn unary<Req, Resp>(&mut self, method: &str, request: Req) -> Result<Resp>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        self.grpc
+            .ready()
+            .await
+            .map_err(|e| ZecKitError::Grpc(format!("Indexer not ready: {}", e)))?;
+
+        let response = self
+            .grpc
+            .unary(tonic::Request::new(request), path(method)?, ProstCodec::default())
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Call a server-streaming method and collect the whole stream.
+    async fn server_streaming<Req, Resp>(&mut self, method: &str, request: Req) -> Result<Vec<Resp>>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        self.grpc
+            .ready()
+            .await
+            .map_err(|e| ZecKitError::Grpc(format!("Indexer not ready: {}", e)))?;
+
+        let mut stream = self
+            .grpc
+            .server_streaming(tonic::Request::new(request), path(method)?, ProstCodec::default())
+            .await?
+            .into_inner();
+
+        let mut messages = Vec::new();
+        while let Some(message) = stream.message().await? {
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+}
+
+fn path(method: &str) -> Result<PathAndQuery> {
+    format!("{}/{}", SERVICE, method)
+        .parse()
+        .map_err(|e| ZecKitError::Grpc(format!("Invalid gRPC method {}: {}", method, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn builds_method_paths() {
+        assert_eq!(
+            path("GetLatestBlock").unwrap().as_str(),
+            "/cash.z.wallet.sdk.rpc.CompactTxStreamer/GetLatestBlock"
+        );
+    }
+
+    #[test]
+    fn skips_fields_not_declared_here() {
+        // Upstream LightdInfo with gitCommit (tag 8), which proto.rs leaves out
+        let mut bytes = LightdInfo { vendor: "zaino".into(), block_height: 42, ..Default::default() }.encode_to_vec();
+        bytes.extend_from_slice(&[0x42, 0x03, b'a', b'b', b'c']);
+
+        let info = LightdInfo::decode(bytes.as_slice()).unwrap();
+        assert_eq!(info.vendor, "zaino");
+        assert_eq!(info.block_height, 42);
+    }
+}
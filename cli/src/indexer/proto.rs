@@ -0,0 +1,172 @@
+//! Messages of lightwalletd's `CompactTxStreamer` service
+//! (`service.proto`, package `cash.z.wallet.sdk.rpc`), written out by hand so
+//! the CLI builds without `protoc`. Field numbers must match the upstream
+//! definitions; fields the CLI doesn't read are left out and skipped on decode.
+
+/// A block by height and/or hash.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BlockId {
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub hash: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ChainSpec {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Empty {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LightdInfo {
+    #[prost(string, tag = "1")]
+    pub version: String,
+    #[prost(string, tag = "2")]
+    pub vendor: String,
+    #[prost(bool, tag = "3")]
+    pub taddr_support: bool,
+    #[prost(string, tag = "4")]
+    pub chain_name: String,
+    #[prost(uint64, tag = "5")]
+    pub sapling_activation_height: u64,
+    #[prost(string, tag = "6")]
+    pub consensus_branch_id: String,
+    #[prost(uint64, tag = "7")]
+    pub block_height: u64,
+    #[prost(uint64, tag = "12")]
+    pub estimated_height: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BlockRange {
+    #[prost(message, optional, tag = "1")]
+    pub start: Option<BlockId>,
+    #[prost(message, optional, tag = "2")]
+    pub end: Option<BlockId>,
+}
+
+/// A transaction by block and index, or by hash (little-endian, as in
+/// `CompactTx::hash`).
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TxFilter {
+    #[prost(message, optional, tag = "1")]
+    pub block: Option<BlockId>,
+    #[prost(uint64, tag = "2")]
+    pub index: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub hash: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawTransaction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub height: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TreeState {
+    #[prost(string, tag = "1")]
+    pub network: String,
+    #[prost(uint64, tag = "2")]
+    pub height: u64,
+    #[prost(string, tag = "3")]
+    pub hash: String,
+    #[prost(uint32, tag = "4")]
+    pub time: u32,
+    #[prost(string, tag = "5")]
+    pub sapling_tree: String,
+    #[prost(string, tag = "6")]
+    pub orchard_tree: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TransparentAddressBlockFilter {
+    #[prost(string, tag = "1")]
+    pub address: String,
+    #[prost(message, optional, tag = "2")]
+    pub range: Option<BlockRange>,
+}
+
+/// Mempool transactions to leave out, by txid suffix.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Exclude {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub txid: Vec<Vec<u8>>,
+}
+
+// compact_formats.proto
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompactBlock {
+    #[prost(uint32, tag = "1")]
+    pub proto_version: u32,
+    #[prost(uint64, tag = "2")]
+    pub height: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub hash: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub prev_hash: Vec<u8>,
+    #[prost(uint32, tag = "5")]
+    pub time: u32,
+    #[prost(bytes = "vec", tag = "6")]
+    pub header: Vec<u8>,
+    #[prost(message, repeated, tag = "7")]
+    pub vtx: Vec<CompactTx>,
+    #[prost(message, optional, tag = "8")]
+    pub chain_metadata: Option<ChainMetadata>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ChainMetadata {
+    #[prost(uint32, tag = "1")]
+    pub sapling_commitment_tree_size: u32,
+    #[prost(uint32, tag = "2")]
+    pub orchard_commitment_tree_size: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompactTx {
+    #[prost(uint64, tag = "1")]
+    pub index: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub hash: Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub fee: u32,
+    #[prost(message, repeated, tag = "4")]
+    pub spends: Vec<CompactSaplingSpend>,
+    #[prost(message, repeated, tag = "5")]
+    pub outputs: Vec<CompactSaplingOutput>,
+    #[prost(message, repeated, tag = "6")]
+    pub actions: Vec<CompactOrchardAction>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompactSaplingSpend {
+    #[prost(bytes = "vec", tag = "1")]
+    pub nf: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompactSaplingOutput {
+    #[prost(bytes = "vec", tag = "1")]
+    pub cmu: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub ephemeral_key: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub ciphertext: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CompactOrchardAction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub nullifier: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub cmx: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub ephemeral_key: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub ciphertext: Vec<u8>,
+}
@@ -1,12 +1,16 @@
 use clap::{Parser, Subcommand};
 use colored::*;
+use std::path::PathBuf;
 use std::process;
 
 mod commands;
 mod docker;
 mod config;
 mod error;
+mod indexer;
+mod testing;
 mod utils;
+mod wait;
 
 #[derive(Parser)]
 #[command(name = "zeckit")]
@@ -21,13 +25,27 @@ struct Cli {
 enum Commands {
     /// Start the ZecKit devnet
     Up {
-        /// Light-client backend: lwd (lightwalletd) or zaino
+        /// Light-client backend: lwd (lightwalletd), zaino, dual (both), or none
         #[arg(short, long, default_value = "none")]
         backend: String,
         
         /// Force fresh start (remove volumes)
         #[arg(short, long)]
         fresh: bool,
+
+        /// Faucet wallet mnemonic, or "random" to generate a new one
+        /// (also read from ZECKIT_FAUCET_SEED)
+        #[arg(long)]
+        seed: Option<String>,
+
+        /// File containing the faucet wallet mnemonic
+        #[arg(long, conflicts_with = "seed")]
+        seed_file: Option<PathBuf>,
+
+        /// Network upgrade activation heights, e.g. nu6=150,nu6_1=300
+        /// (NU5 defaults to 1; others stay inactive unless given)
+        #[arg(long, value_name = "UPGRADE=HEIGHT,...")]
+        activation: Option<config::activation::ActivationHeights>,
     },
     
     /// Stop the ZecKit devnet
@@ -41,7 +59,108 @@ enum Commands {
     Status,
     
     /// Run smoke tests
-    Test,
+    Test {
+        /// Only run tests whose name contains, or tag equals, PATTERN (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        filter: Vec<String>,
+
+        /// Leave out tests matching PATTERN (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        skip: Vec<String>,
+
+        /// List registered tests and which would run
+        #[arg(long)]
+        list: bool,
+
+        /// Show details of passing tests too
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Treat skipped tests as failures
+        #[arg(long)]
+        strict: bool,
+
+        /// Write a report: junit:PATH, json:PATH or tap[:PATH] (stdout without a path; repeatable)
+        #[arg(long, value_name = "FORMAT[:PATH]")]
+        report: Vec<testing::report::ReportSpec>,
+    },
+
+    /// Manage the faucet wallet
+    Wallet {
+        #[command(subcommand)]
+        command: WalletCommands,
+    },
+
+    /// Generate test fixtures from the running devnet
+    Fixtures {
+        #[command(subcommand)]
+        command: FixturesCommands,
+    },
+
+    /// Diagnose common problems with the host and the devnet
+    Doctor,
+
+    /// Roll back the last N blocks by mining a longer competing fork
+    Reorg {
+        /// Number of blocks to orphan (1-99)
+        #[arg(long)]
+        depth: u64,
+
+        /// Leave the fork node running afterwards
+        #[arg(long)]
+        keep_fork: bool,
+    },
+
+    /// Run Zaino and lightwalletd side by side and diff their gRPC answers
+    CompareBackends {
+        /// Number of blocks below the common tip to compare
+        #[arg(long, default_value_t = 10)]
+        blocks: u64,
+
+        /// Transparent address for GetTaddressTxids (default: the miner address)
+        #[arg(long)]
+        address: Option<String>,
+
+        /// Also write the report as JSON to this file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Leave both indexers running afterwards
+        #[arg(long)]
+        keep: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalletCommands {
+    /// Rebuild the faucet wallet from its seed and rescan (keeps chain state)
+    Rescan {
+        /// Block height to rescan from
+        #[arg(long)]
+        from_height: Option<u32>,
+
+        /// Also clear the faucet's transaction history
+        #[arg(long, conflicts_with = "from_height")]
+        reset: bool,
+
+        /// Return immediately instead of waiting for the rescan to finish
+        #[arg(long)]
+        no_wait: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum FixturesCommands {
+    /// Write addresses, viewing keys and confirmed transactions to a fixture file
+    Generate {
+        /// Output file (default: fixtures/zeckit-fixtures.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Only write addresses and keys; don't create transactions
+        #[arg(long)]
+        no_transactions: bool,
+    },
 }
 
 #[tokio::main]
@@ -49,8 +168,8 @@ async fn main() {
     let cli = Cli::parse();
     
     let result = match cli.command {
-        Commands::Up { backend, fresh } => {
-            commands::up::execute(backend, fresh).await
+        Commands::Up { backend, fresh, seed, seed_file, activation } => {
+            commands::up::execute(backend, fresh, seed, seed_file, activation.unwrap_or_default()).await
         }
         Commands::Down { purge } => {
             commands::down::execute(purge).await
@@ -58,8 +177,34 @@ async fn main() {
         Commands::Status => {
             commands::status::execute().await
         }
-        Commands::Test => {
-            commands::test::execute().await
+        Commands::Test { filter, skip, list, verbose, strict, report } => {
+            commands::test::execute(commands::test::TestOptions {
+                filters: filter,
+                skips: skip,
+                list,
+                verbose,
+                strict,
+                reports: report,
+            }).await
+        }
+        Commands::Wallet { command } => match command {
+            WalletCommands::Rescan { from_height, reset, no_wait } => {
+                commands::wallet::rescan(from_height, reset, !no_wait).await
+            }
+        },
+        Commands::Fixtures { command } => match command {
+            FixturesCommands::Generate { output, no_transactions } => {
+                commands::fixtures::generate(output, !no_transactions).await
+            }
+        },
+        Commands::Doctor => {
+            commands::doctor::execute().await
+        }
+        Commands::Reorg { depth, keep_fork } => {
+            commands::reorg::execute(depth, keep_fork).await
+        }
+        Commands::CompareBackends { blocks, address, output, keep } => {
+            commands::compare::execute(commands::compare::CompareOptions { blocks, address, output, keep }).await
         }
     };
     
@@ -0,0 +1,97 @@
+use super::{Outcome, TestBody, TestCase, TestContext};
+use crate::error::{Result, ZecKitError};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use tokio::process::Command;
+use tokio::time::Duration;
+
+/// Project-level file where teams declare their own tests.
+pub const CUSTOM_TESTS_FILE: &str = "zeckit-tests.json";
+
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// Exit status a custom test uses to report a skip (the automake convention).
+const SKIP_EXIT_CODE: i32 = 77;
+
+/// Output lines kept from a failing command.
+const OUTPUT_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Deserialize)]
+struct CustomTestFile {
+    tests: Vec<CustomTestSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomTestSpec {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    command: String,
+    #[serde(default = "default_timeout")]
+    timeout_secs: u64,
+}
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+pub fn load(path: &Path) -> Result<Vec<TestCase>> {
+    let content = fs::read_to_string(path)?;
+    let file: CustomTestFile = serde_json::from_str(&content)
+        .map_err(|e| ZecKitError::Config(format!("Invalid {}: {}", path.display(), e)))?;
+
+    Ok(file.tests
+        .into_iter()
+        .map(|spec| TestCase {
+            name: spec.name,
+            description: spec.description,
+            tags: spec.tags,
+            depends_on: spec.depends_on,
+            body: TestBody::Command {
+                command: spec.command,
+                timeout_secs: spec.timeout_secs,
+            },
+        })
+        .collect())
+}
+
+/// Run a custom test's command with `sh -c` from the project root. The
+/// devnet endpoints are passed as `ZECKIT_FAUCET_URL` and `ZECKIT_ZEBRA_URL`.
+/// Exit status 77 skips the test, with the last output line as the reason.
+pub async fn run_command(ctx: &mut TestContext, command: &str, timeout_secs: u64) -> Result<Outcome> {
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(crate::utils::project_dir()?)
+        .env("ZECKIT_FAUCET_URL", "http://127.0.0.1:8080")
+        .env("ZECKIT_ZEBRA_URL", "http://127.0.0.1:8232")
+        .kill_on_drop(true)
+        .output();
+
+    let output = tokio::time::timeout(Duration::from_secs(timeout_secs), child)
+        .await
+        .map_err(|_| ZecKitError::HealthCheck(format!("Timed out after {}s", timeout_secs)))??;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = stdout.lines().chain(stderr.lines()).collect();
+    for line in &lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..] {
+        ctx.note(*line);
+    }
+
+    if output.status.code() == Some(SKIP_EXIT_CODE) {
+        let reason = lines.last().map(|l| l.trim()).filter(|l| !l.is_empty()).unwrap_or("skipped by command");
+        return Ok(Outcome::Skipped(reason.to_string()));
+    }
+
+    if !output.status.success() {
+        return Err(ZecKitError::HealthCheck(format!("Command exited with {}", output.status)));
+    }
+
+    Ok(Outcome::Passed)
+}
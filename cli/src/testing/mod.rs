@@ -0,0 +1,127 @@
+pub mod custom;
+pub mod registry;
+pub mod report;
+pub mod smoke;
+
+use crate::error::Result;
+use reqwest::Client;
+use serde_json::{Map, Value};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Body of a built-in test. Details go to `ctx.note` rather than stdout so
+/// the runner decides how to show them.
+pub type TestFn = for<'a> fn(&'a mut TestContext) -> BoxFuture<'a, Result<Outcome>>;
+
+/// How a test that ran to completion ended. Errors are failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// The test could not check what it is for, e.g. the node cannot mine.
+    Skipped(String),
+}
+
+#[derive(Clone)]
+pub enum TestBody {
+    Builtin(TestFn),
+    /// Shell command from `zeckit-tests.json`; exit status 0 passes and
+    /// 77 skips.
+    Command { command: String, timeout_secs: u64 },
+}
+
+#[derive(Clone)]
+pub struct TestCase {
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    /// Tests that must pass before this one runs.
+    pub depends_on: Vec<String>,
+    pub body: TestBody,
+}
+
+impl TestCase {
+    pub fn builtin(name: &str, description: &str, tags: &[&str], depends_on: &[&str], run: TestFn) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            body: TestBody::Builtin(run),
+        }
+    }
+
+    pub fn description_or_name(&self) -> &str {
+        if self.description.is_empty() {
+            &self.name
+        } else {
+            &self.description
+        }
+    }
+
+    pub fn source(&self) -> &'static str {
+        match self.body {
+            TestBody::Builtin(_) => "builtin",
+            TestBody::Command { .. } => "custom",
+        }
+    }
+
+    /// A `--filter`/`--skip` pattern matches a tag exactly or part of the name.
+    pub fn matches(&self, pattern: &str) -> bool {
+        self.name.contains(pattern) || self.tags.iter().any(|t| t == pattern)
+    }
+}
+
+/// State shared by all tests in a run.
+pub struct TestContext {
+    pub client: Client,
+    notes: Vec<String>,
+    context: Map<String, Value>,
+}
+
+impl TestContext {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            notes: Vec::new(),
+            context: Map::new(),
+        }
+    }
+
+    /// Record a detail line for the current test.
+    pub fn note(&mut self, line: impl Into<String>) {
+        self.notes.push(line.into());
+    }
+
+    /// Capture a value for reports, such as a txid or a balance.
+    pub fn record(&mut self, key: &str, value: impl Into<Value>) {
+        self.context.insert(key.to_string(), value.into());
+    }
+
+    /// Notes and captured context of the test that just finished.
+    pub fn take_output(&mut self) -> (Vec<String>, Map<String, Value>) {
+        (std::mem::take(&mut self.notes), std::mem::take(&mut self.context))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed(String),
+    Skipped(String),
+    /// Not run because a dependency did not pass.
+    Blocked(String),
+}
+
+pub struct TestResult {
+    pub name: String,
+    pub description: String,
+    pub source: &'static str,
+    pub tags: Vec<String>,
+    pub status: TestStatus,
+    pub duration: Duration,
+    pub notes: Vec<String>,
+    pub context: Map<String, Value>,
+}
@@ -0,0 +1,137 @@
+use super::{custom, smoke, TestCase};
+use crate::error::{Result, ZecKitError};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// All known tests, in run order. A test can only depend on tests registered
+/// before it, which keeps the order valid and rules out cycles.
+pub struct Registry {
+    tests: Vec<TestCase>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self { tests: Vec::new() }
+    }
+
+    /// Built-in smoke tests followed by the project's custom tests, if any.
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let mut registry = Self::new();
+        for test in smoke::tests() {
+            registry.register(test)?;
+        }
+
+        let custom_file = project_dir.join(custom::CUSTOM_TESTS_FILE);
+        if custom_file.exists() {
+            for test in custom::load(&custom_file)? {
+                registry.register(test)?;
+            }
+        }
+
+        Ok(registry)
+    }
+
+    pub fn register(&mut self, test: TestCase) -> Result<()> {
+        if self.get(&test.name).is_some() {
+            return Err(ZecKitError::Config(format!("Duplicate test name: {}", test.name)));
+        }
+        if let Some(missing) = test.depends_on.iter().find(|d| self.get(d).is_none()) {
+            return Err(ZecKitError::Config(format!(
+                "Test {} depends on unknown or later test {}",
+                test.name, missing
+            )));
+        }
+
+        self.tests.push(test);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TestCase> {
+        self.tests.iter().find(|t| t.name == name)
+    }
+
+    pub fn tests(&self) -> &[TestCase] {
+        &self.tests
+    }
+
+    /// Tests to run, in registration order.
+    ///
+    /// A test is selected when it matches any `filters` pattern (all tests when
+    /// there are none) and no `skips` pattern. Dependencies of selected tests
+    /// are pulled in unless they are skipped themselves.
+    pub fn select(&self, filters: &[String], skips: &[String]) -> Vec<&TestCase> {
+        let skipped = |t: &TestCase| skips.iter().any(|p| t.matches(p));
+
+        let mut selected: HashSet<&str> = self.tests
+            .iter()
+            .filter(|t| filters.is_empty() || filters.iter().any(|p| t.matches(p)))
+            .filter(|t| !skipped(t))
+            .map(|t| t.name.as_str())
+            .collect();
+
+        // Dependencies always come earlier, so one backwards pass is transitive
+        for test in self.tests.iter().rev() {
+            if selected.contains(test.name.as_str()) {
+                for dep in &test.depends_on {
+                    if let Some(dep) = self.get(dep).filter(|d| !skipped(d)) {
+                        selected.insert(dep.name.as_str());
+                    }
+                }
+            }
+        }
+
+        self.tests
+            .iter()
+            .filter(|t| selected.contains(t.name.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestBody;
+
+    fn case(name: &str, tags: &[&str], depends_on: &[&str]) -> TestCase {
+        TestCase {
+            name: name.to_string(),
+            description: String::new(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            body: TestBody::Command { command: "true".to_string(), timeout_secs: 1 },
+        }
+    }
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(case("health", &["faucet"], &[])).unwrap();
+        registry.register(case("sync", &["wallet"], &["health"])).unwrap();
+        registry.register(case("shield", &["wallet"], &["sync"])).unwrap();
+        registry.register(case("send", &["wallet"], &["shield"])).unwrap();
+        registry
+    }
+
+    fn names(tests: Vec<&TestCase>) -> Vec<&str> {
+        tests.into_iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[test]
+    fn filter_pulls_in_dependencies() {
+        let registry = registry();
+        let selected = registry.select(&["shield".to_string()], &[]);
+        assert_eq!(names(selected), vec!["health", "sync", "shield"]);
+    }
+
+    #[test]
+    fn skip_wins_over_filter_and_dependencies() {
+        let registry = registry();
+        let selected = registry.select(&["wallet".to_string()], &["sync".to_string(), "send".to_string()]);
+        assert_eq!(names(selected), vec!["shield"]);
+    }
+
+    #[test]
+    fn rejects_unknown_dependency() {
+        let mut registry = Registry::new();
+        assert!(registry.register(case("send", &[], &["shield"])).is_err());
+    }
+}
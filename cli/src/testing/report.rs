@@ -0,0 +1,278 @@
+use super::{TestResult, TestStatus};
+use crate::error::{Result, ZecKitError};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Json,
+    Tap,
+}
+
+/// A `--report FORMAT[:PATH]` argument. Without a path the report goes to stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSpec {
+    pub format: ReportFormat,
+    pub path: Option<PathBuf>,
+}
+
+impl FromStr for ReportSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (format, path) = match s.split_once(':') {
+            Some((format, path)) => (format, Some(PathBuf::from(path))),
+            None => (s, None),
+        };
+
+        let format = match format {
+            "junit" => ReportFormat::Junit,
+            "json" => ReportFormat::Json,
+            "tap" => ReportFormat::Tap,
+            other => return Err(format!("unknown report format {:?} (expected junit, json or tap)", other)),
+        };
+
+        Ok(Self { format, path })
+    }
+}
+
+impl ReportSpec {
+    pub fn write(&self, results: &[TestResult], total: Duration) -> Result<()> {
+        let report = match self.format {
+            ReportFormat::Junit => junit(results, total),
+            ReportFormat::Json => serde_json::to_string_pretty(&json_report(results, total))?,
+            ReportFormat::Tap => tap(results),
+        };
+
+        match &self.path {
+            Some(path) => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, report)
+                    .map_err(|e| ZecKitError::Config(format!("Could not write report {:?}: {}", path, e)))
+            }
+            None => {
+                println!("{}", report);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn status_label(status: &TestStatus) -> &'static str {
+    match status {
+        TestStatus::Passed => "passed",
+        TestStatus::Failed(_) => "failed",
+        TestStatus::Skipped(_) => "skipped",
+        TestStatus::Blocked(_) => "blocked",
+    }
+}
+
+fn json_report(results: &[TestResult], total: Duration) -> serde_json::Value {
+    let tests: Vec<_> = results
+        .iter()
+        .map(|r| {
+            let message = match &r.status {
+                TestStatus::Passed => None,
+                TestStatus::Failed(message)
+                | TestStatus::Skipped(message)
+                | TestStatus::Blocked(message) => Some(message),
+            };
+            json!({
+                "name": r.name,
+                "description": r.description,
+                "source": r.source,
+                "tags": r.tags,
+                "status": status_label(&r.status),
+                "message": message,
+                "duration_ms": r.duration.as_millis() as u64,
+                "notes": r.notes,
+                "context": r.context
+            })
+        })
+        .collect();
+
+    json!({
+        "suite": "zeckit",
+        "total": results.len(),
+        "passed": results.iter().filter(|r| r.status == TestStatus::Passed).count(),
+        "failed": results.iter().filter(|r| matches!(r.status, TestStatus::Failed(_))).count(),
+        "skipped": results.iter().filter(|r| matches!(r.status, TestStatus::Skipped(_))).count(),
+        "blocked": results.iter().filter(|r| matches!(r.status, TestStatus::Blocked(_))).count(),
+        "duration_ms": total.as_millis() as u64,
+        "tests": tests
+    })
+}
+
+fn junit(results: &[TestResult], total: Duration) -> String {
+    let failures = results.iter().filter(|r| matches!(r.status, TestStatus::Failed(_))).count();
+    let skipped = results
+        .iter()
+        .filter(|r| matches!(r.status, TestStatus::Skipped(_) | TestStatus::Blocked(_)))
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"zeckit\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        results.len(), failures, skipped, total.as_secs_f64()
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"zeckit\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+        results.len(), failures, skipped, total.as_secs_f64()
+    );
+
+    for r in results {
+        let _ = writeln!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"zeckit.{}\" time=\"{:.3}\">",
+            escape_xml(&r.name), r.source, r.duration.as_secs_f64()
+        );
+
+        if !r.context.is_empty() {
+            xml.push_str("      <properties>\n");
+            for (key, value) in &r.context {
+                let value = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+                let _ = writeln!(
+                    xml,
+                    "        <property name=\"{}\" value=\"{}\"/>",
+                    escape_xml(key), escape_xml(&value)
+                );
+            }
+            xml.push_str("      </properties>\n");
+        }
+
+        match &r.status {
+            TestStatus::Passed => {}
+            TestStatus::Failed(message) => {
+                let _ = writeln!(
+                    xml,
+                    "      <failure message=\"{}\">{}</failure>",
+                    escape_xml(message), escape_xml(&r.notes.join("\n"))
+                );
+            }
+            TestStatus::Skipped(reason) | TestStatus::Blocked(reason) => {
+                let _ = writeln!(xml, "      <skipped message=\"{}\"/>", escape_xml(reason));
+            }
+        }
+
+        if !r.notes.is_empty() {
+            let _ = writeln!(xml, "      <system-out>{}</system-out>", escape_xml(&r.notes.join("\n")));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>");
+    xml
+}
+
+fn tap(results: &[TestResult]) -> String {
+    let mut out = String::from("TAP version 13\n");
+    let _ = writeln!(out, "1..{}", results.len());
+
+    for (i, r) in results.iter().enumerate() {
+        match &r.status {
+            TestStatus::Passed => {
+                let _ = writeln!(out, "ok {} - {}", i + 1, r.name);
+            }
+            TestStatus::Failed(_) => {
+                let _ = writeln!(out, "not ok {} - {}", i + 1, r.name);
+            }
+            TestStatus::Skipped(reason) | TestStatus::Blocked(reason) => {
+                let _ = writeln!(out, "ok {} - {} # SKIP {}", i + 1, r.name, reason);
+            }
+        }
+
+        // YAML diagnostics block
+        out.push_str("  ---\n");
+        let _ = writeln!(out, "  duration_ms: {}", r.duration.as_millis());
+        if let TestStatus::Failed(message) = &r.status {
+            let _ = writeln!(out, "  message: {}", json!(message));
+        }
+        for (key, value) in &r.context {
+            let _ = writeln!(out, "  {}: {}", key, value);
+        }
+        if !r.notes.is_empty() {
+            out.push_str("  notes:\n");
+            for note in &r.notes {
+                let _ = writeln!(out, "    - {}", json!(note));
+            }
+        }
+        out.push_str("  ...\n");
+    }
+
+    out.trim_end().to_string()
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Map;
+
+    fn result(name: &str, status: TestStatus) -> TestResult {
+        let mut context = Map::new();
+        context.insert("txid".to_string(), json!("abc<1>"));
+        TestResult {
+            name: name.to_string(),
+            description: String::new(),
+            source: "builtin",
+            tags: vec![],
+            status,
+            duration: Duration::from_millis(1500),
+            notes: vec!["detail".to_string()],
+            context,
+        }
+    }
+
+    #[test]
+    fn parses_report_specs() {
+        assert_eq!(
+            "junit:out/results.xml".parse::<ReportSpec>().unwrap(),
+            ReportSpec { format: ReportFormat::Junit, path: Some(PathBuf::from("out/results.xml")) }
+        );
+        assert_eq!("tap".parse::<ReportSpec>().unwrap().path, None);
+        assert!("html:out.html".parse::<ReportSpec>().is_err());
+    }
+
+    #[test]
+    fn junit_escapes_and_counts() {
+        let results = vec![
+            result("a", TestStatus::Passed),
+            result("b", TestStatus::Failed("boom & bust".to_string())),
+        ];
+        let xml = junit(&results, Duration::from_secs(3));
+
+        assert!(xml.contains("tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<failure message=\"boom &amp; bust\">"));
+        assert!(xml.contains("value=\"abc&lt;1&gt;\""));
+        assert!(xml.contains("time=\"1.500\""));
+    }
+
+    #[test]
+    fn tap_marks_blocked_as_skip() {
+        let results = vec![
+            result("a", TestStatus::Failed("boom".to_string())),
+            result("b", TestStatus::Blocked("dependency a did not pass".to_string())),
+        ];
+        let out = tap(&results);
+
+        assert!(out.contains("1..2"));
+        assert!(out.contains("not ok 1 - a"));
+        assert!(out.contains("ok 2 - b # SKIP dependency a did not pass"));
+    }
+}
@@ -0,0 +1,428 @@
+use super::{BoxFuture, Outcome, TestCase, TestContext};
+use crate::error::{Result, ZecKitError};
+use crate::wait::Wait;
+use serde_json::Value;
+use tokio::time::Duration;
+
+/// The built-in smoke tests, in run order.
+pub fn tests() -> Vec<TestCase> {
+    vec![
+        TestCase::builtin("zebra-rpc", "Zebra RPC connectivity", &["zebra", "rpc"], &[], zebra_rpc),
+        TestCase::builtin("faucet-health", "Faucet health check", &["faucet"], &[], faucet_health),
+        TestCase::builtin(
+            "faucet-address",
+            "Faucet address retrieval",
+            &["faucet", "address"],
+            &["faucet-health"],
+            faucet_address,
+        ),
+        TestCase::builtin(
+            "wallet-sync",
+            "Wallet sync capability",
+            &["wallet", "sync"],
+            &["zebra-rpc", "faucet-health"],
+            wallet_sync,
+        ),
+        TestCase::builtin(
+            "wallet-shield",
+            "Wallet balance and shield",
+            &["wallet", "shield"],
+            &["wallet-sync"],
+            wallet_shield,
+        ),
+        TestCase::builtin(
+            "shielded-send",
+            "Shielded send (E2E)",
+            &["wallet", "send", "e2e"],
+            &["faucet-address", "wallet-shield"],
+            shielded_send,
+        ),
+    ]
+}
+
+fn zebra_rpc(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        let resp = ctx.client
+            .post("http://127.0.0.1:8232")
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "test",
+                "method": "getblockcount",
+                "params": []
+            }))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(ZecKitError::HealthCheck("Zebra RPC not responding".into()));
+        }
+
+        Ok(Outcome::Passed)
+    })
+}
+
+fn faucet_health(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        let resp = ctx.client
+            .get("http://127.0.0.1:8080/health")
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(ZecKitError::HealthCheck("Faucet health check failed".into()));
+        }
+
+        let json: Value = resp.json().await?;
+
+        // Verify key health fields
+        if json.get("status").and_then(|v| v.as_str()) != Some("healthy") {
+            return Err(ZecKitError::HealthCheck("Faucet not reporting healthy status".into()));
+        }
+
+        Ok(Outcome::Passed)
+    })
+}
+
+fn faucet_address(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        let resp = ctx.client
+            .get("http://127.0.0.1:8080/address")
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(ZecKitError::HealthCheck("Could not get faucet address".into()));
+        }
+
+        let json: Value = resp.json().await?;
+
+        // Verify both address types are present
+        if json.get("unified_address").is_none() {
+            return Err(ZecKitError::HealthCheck("Missing unified address in response".into()));
+        }
+
+        if json.get("transparent_address").is_none() {
+            return Err(ZecKitError::HealthCheck("Missing transparent address in response".into()));
+        }
+
+        Ok(Outcome::Passed)
+    })
+}
+
+fn wallet_sync(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        let resp = ctx.client
+            .post("http://127.0.0.1:8080/sync")
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(ZecKitError::HealthCheck("Wallet sync failed".into()));
+        }
+
+        let json: Value = resp.json().await?;
+
+        if json.get("status").and_then(|v| v.as_str()) != Some("synced") {
+            return Err(ZecKitError::HealthCheck("Wallet sync did not complete successfully".into()));
+        }
+
+        Ok(Outcome::Passed)
+    })
+}
+
+/// Regtest coinbase needs 100 confirmations; one more block makes it spendable.
+const COINBASE_MATURITY_BLOCKS: u32 = 101;
+
+/// Smallest transparent balance worth shielding (fee plus some value).
+const MIN_SHIELD_AMOUNT: f64 = 0.0002;
+
+/// Orchard balance the shielded send needs.
+const MIN_SEND_BALANCE: f64 = 0.1;
+
+fn wallet_shield(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        let before = get_wallet_balance_via_api(ctx).await?;
+        ctx.note(format!("Transparent: {} ZEC", before.transparent));
+        ctx.note(format!("Orchard: {} ZEC", before.orchard));
+        ctx.record("transparent_before", before.transparent);
+        ctx.record("orchard_before", before.orchard);
+
+        // Create transparent funds to shield by mining mature coinbase
+        if before.transparent < MIN_SHIELD_AMOUNT {
+            ctx.note(format!("Mining {} blocks for mature coinbase", COINBASE_MATURITY_BLOCKS));
+            if let Err(e) = mine(ctx, COINBASE_MATURITY_BLOCKS).await {
+                return Ok(Outcome::Skipped(format!("cannot mine on this node: {}", e)));
+            }
+            ctx.record("blocks_mined", COINBASE_MATURITY_BLOCKS);
+            sync(ctx).await?;
+        }
+
+        let funded = get_wallet_balance_via_api(ctx).await?;
+        if funded.transparent < MIN_SHIELD_AMOUNT {
+            // The faucet's background auto-shield may have beaten us to it
+            if funded.orchard > before.orchard {
+                ctx.note("Transparent funds were already shielded by the faucet's auto-shield");
+                ctx.record("orchard_after", funded.orchard);
+                return Ok(Outcome::Passed);
+            }
+            return Err(ZecKitError::HealthCheck(format!(
+                "No transparent funds to shield after mining ({} ZEC)",
+                funded.transparent
+            )));
+        }
+
+        ctx.note(format!("Shielding {} ZEC to Orchard via API", funded.transparent));
+        let shield_resp = ctx.client
+            .post("http://127.0.0.1:8080/shield")
+            .send()
+            .await?;
+
+        if !shield_resp.status().is_success() {
+            let error_text = shield_resp.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ZecKitError::HealthCheck(format!("Shield API call failed: {}", error_text)));
+        }
+
+        let shield_json: Value = shield_resp.json().await?;
+        let status = shield_json.get("status").and_then(|v| v.as_str()).unwrap_or("unknown");
+
+        let txid = shield_json.get("txid").and_then(|v| v.as_str());
+        match status {
+            "shielded" => {
+                if let Some(txid) = txid {
+                    ctx.note(format!("Shield TXID: {}", txid));
+                    ctx.record("txid", txid);
+                }
+            }
+            "no_funds" => ctx.note("Shield found no funds; checking for an auto-shield"),
+            _ => {
+                return Err(ZecKitError::HealthCheck(format!(
+                    "Unexpected shield status {:?}: {}",
+                    status,
+                    shield_json.get("message").and_then(|v| v.as_str()).unwrap_or_default()
+                )));
+            }
+        }
+
+        // Mine the shield transaction and let the wallet see it
+        confirm(ctx, txid).await?;
+
+        let after = get_wallet_balance_via_api(ctx).await?;
+        ctx.record("transparent_after", after.transparent);
+        ctx.record("orchard_after", after.orchard);
+        ctx.note(format!("Transparent after shield: {} ZEC (was {})", after.transparent, funded.transparent));
+        ctx.note(format!("Orchard after shield: {} ZEC (was {})", after.orchard, funded.orchard));
+
+        // confirm() waited for the wallet to sync past the shield, so an
+        // unchanged balance means the shield didn't land
+        if after.orchard > funded.orchard || after.transparent < funded.transparent {
+            Ok(Outcome::Passed)
+        } else {
+            Err(ZecKitError::HealthCheck(format!(
+                "Balance unchanged after the shield was confirmed (transparent {} ZEC, orchard {} ZEC)",
+                after.transparent, after.orchard
+            )))
+        }
+    })
+}
+
+#[derive(Debug)]
+struct WalletBalance {
+    transparent: f64,
+    orchard: f64,
+}
+
+/// Get wallet balance using the /stats endpoint
+async fn get_wallet_balance_via_api(ctx: &TestContext) -> Result<WalletBalance> {
+    let resp = ctx.client
+        .get("http://127.0.0.1:8080/stats")
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck("Failed to get balance from stats endpoint".into()));
+    }
+
+    let json: Value = resp.json().await?;
+
+    let transparent = json.get("transparent_balance")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+
+    let orchard = json.get("orchard_balance")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0);
+
+    Ok(WalletBalance {
+        transparent,
+        orchard,
+    })
+}
+
+/// Mine `blocks` regtest blocks through Zebra's `generate` RPC.
+async fn mine(ctx: &TestContext, blocks: u32) -> Result<()> {
+    let resp = ctx.client
+        .post("http://127.0.0.1:8232")
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "generate",
+            "method": "generate",
+            "params": [blocks]
+        }))
+        .timeout(Duration::from_secs(60 + blocks as u64))
+        .send()
+        .await?;
+
+    let json: Value = resp.json().await?;
+    if !json["error"].is_null() {
+        return Err(ZecKitError::HealthCheck(format!("generate failed: {}", json["error"])));
+    }
+
+    Ok(())
+}
+
+async fn sync(ctx: &TestContext) -> Result<()> {
+    let resp = ctx.client
+        .post("http://127.0.0.1:8080/sync")
+        .timeout(Duration::from_secs(180))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck("Wallet sync failed".into()));
+    }
+
+    Ok(())
+}
+
+/// How long a transaction gets to be mined and seen by the faucet wallet.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Wait until `txid` is mined and the wallet has synced past it. Without a
+/// txid (e.g. auto-shield already ran), mine a block and wait for the wallet
+/// to reach the tip.
+async fn confirm(ctx: &TestContext, txid: Option<&str>) -> Result<()> {
+    let wait = Wait::new(&ctx.client, CONFIRMATION_TIMEOUT).mining();
+    match txid {
+        Some(txid) => wait.for_confirmed(txid).await.map(|_| ()),
+        None => {
+            mine(ctx, 1).await?;
+            wait.for_wallet_at_tip().await.map(|_| ())
+        }
+    }
+}
+
+/// Make sure the faucet holds at least `min` ZEC in Orchard by mining and
+/// shielding fresh coinbase. Returns a skip when the node cannot mine.
+async fn ensure_orchard_funds(ctx: &mut TestContext, min: f64) -> Result<Option<Outcome>> {
+    let balance = get_wallet_balance_via_api(ctx).await?;
+    if balance.orchard >= min {
+        return Ok(None);
+    }
+
+    ctx.note(format!("Orchard balance {} ZEC is below {}; mining and shielding", balance.orchard, min));
+    if balance.transparent < MIN_SHIELD_AMOUNT {
+        if let Err(e) = mine(ctx, COINBASE_MATURITY_BLOCKS).await {
+            return Ok(Some(Outcome::Skipped(format!("cannot mine on this node: {}", e))));
+        }
+        sync(ctx).await?;
+    }
+
+    // A "no_funds" answer means auto-shield already moved the coinbase
+    let resp = ctx.client.post("http://127.0.0.1:8080/shield").send().await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(ZecKitError::HealthCheck(format!("Shield API call failed ({}): {}", status, body)));
+    }
+    let shield: Value = resp.json().await?;
+    confirm(ctx, shield.get("txid").and_then(|v| v.as_str())).await?;
+
+    let balance = get_wallet_balance_via_api(ctx).await?;
+    if balance.orchard < min {
+        return Err(ZecKitError::HealthCheck(format!(
+            "Orchard balance still {} ZEC after mining and shielding (need {})",
+            balance.orchard, min
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Shielded send (E2E golden flow): send shielded funds to another wallet
+fn shielded_send(ctx: &mut TestContext) -> BoxFuture<'_, Result<Outcome>> {
+    Box::pin(async move {
+        // Step 1: Make sure the faucet has shielded funds
+        if let Some(skip) = ensure_orchard_funds(ctx, MIN_SEND_BALANCE).await? {
+            return Ok(skip);
+        }
+
+        let balance = get_wallet_balance_via_api(ctx).await?;
+        ctx.note(format!("Faucet Orchard balance: {} ZEC", balance.orchard));
+        ctx.record("orchard_before", balance.orchard);
+
+        // Extra sync to ensure wallet can spend the funds
+        sync(ctx).await?;
+
+        // Step 2: Get a test recipient address (using faucet's own UA for simplicity)
+        let addr_resp = ctx.client
+            .get("http://127.0.0.1:8080/address")
+            .send()
+            .await?;
+
+        if !addr_resp.status().is_success() {
+            return Err(ZecKitError::HealthCheck("Failed to get recipient address".into()));
+        }
+
+        let addr_json: Value = addr_resp.json().await?;
+        let recipient_address = addr_json.get("unified_address")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ZecKitError::HealthCheck("No unified address in response".into()))?
+            .to_string();
+
+        ctx.note(format!("Recipient: {}", recipient_address));
+        ctx.record("recipient", recipient_address.as_str());
+
+        // Step 3: Perform shielded send
+        let send_amount = 0.05;
+        let send_resp = ctx.client
+            .post("http://127.0.0.1:8080/send")
+            .json(&serde_json::json!({
+                "address": recipient_address,
+                "amount": send_amount,
+                "memo": "ZecKit smoke test - shielded send"
+            }))
+            .send()
+            .await?;
+
+        if !send_resp.status().is_success() {
+            let error_text = send_resp.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ZecKitError::HealthCheck(format!("Shielded send failed: {}", error_text)));
+        }
+
+        let send_json: Value = send_resp.json().await?;
+
+        // Step 4: Verify transaction
+        let status = send_json.get("status").and_then(|v| v.as_str());
+
+        if status == Some("sent") {
+            if let Some(txid) = send_json.get("txid").and_then(|v| v.as_str()) {
+                ctx.note(format!("Sent {} ZEC, TXID: {}", send_amount, txid));
+                ctx.record("txid", txid);
+            }
+            ctx.record("amount", send_amount);
+            if let Some(new_balance) = send_json.get("orchard_balance").and_then(|v| v.as_f64()) {
+                ctx.note(format!("New Orchard balance: {} ZEC (was {})", new_balance, balance.orchard));
+                ctx.record("orchard_after", new_balance);
+            }
+            Ok(Outcome::Passed)
+        } else {
+            if let Some(msg) = send_json.get("message").and_then(|v| v.as_str()) {
+                ctx.note(format!("Message: {}", msg));
+            }
+            Err(ZecKitError::HealthCheck(format!(
+                "Shielded send did not complete as expected (status {:?})",
+                status
+            )))
+        }
+    })
+}
@@ -1,7 +1,17 @@
+use std::path::PathBuf;
 use std::process::Command;
 
-/// Check if Docker is installed and running
-#[allow(dead_code)]
+/// Project root: the current directory, or its parent when run from `cli/`
+pub fn project_dir() -> std::io::Result<PathBuf> {
+    let current_dir = std::env::current_dir()?;
+    if current_dir.ends_with("cli") {
+        Ok(current_dir.parent().unwrap().to_path_buf())
+    } else {
+        Ok(current_dir)
+    }
+}
+
+/// Check if the Docker CLI is installed
 pub fn check_docker() -> bool {
     Command::new("docker")
         .arg("--version")
@@ -11,7 +21,6 @@ pub fn check_docker() -> bool {
 }
 
 /// Check if Docker Compose is available
-#[allow(dead_code)]
 pub fn check_docker_compose() -> bool {
     Command::new("docker")
         .arg("compose")
@@ -31,7 +40,6 @@ pub fn print_banner(title: &str) {
 }
 
 /// Format bytes for display
-#[allow(dead_code)]
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -0,0 +1,268 @@
+use crate::error::{Result, ZecKitError};
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+const ZEBRA_RPC: &str = "http://127.0.0.1:8232";
+const FAUCET_API: &str = "http://127.0.0.1:8080";
+
+/// Where a transaction is, as far as Zebra knows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxState {
+    /// Not in the mempool or the chain (yet).
+    Unknown,
+    InMempool,
+    Mined(u64),
+}
+
+/// Polls Zebra and the faucet until a condition holds or the deadline passes.
+///
+/// Replaces fixed sleeps: returns as soon as the chain and wallet are where
+/// they need to be, and fails with [`ZecKitError::WaitTimeout`] describing the
+/// last state seen when they never get there.
+pub struct Wait {
+    client: Client,
+    timeout: Duration,
+    interval: Duration,
+    mine: bool,
+}
+
+impl Wait {
+    pub fn new(client: &Client, timeout: Duration) -> Self {
+        Self {
+            client: client.clone(),
+            timeout,
+            interval: Duration::from_secs(1),
+            mine: false,
+        }
+    }
+
+    /// Mine a block on every poll while the transaction sits in the mempool,
+    /// for nodes without the internal miner.
+    pub fn mining(mut self) -> Self {
+        self.mine = true;
+        self
+    }
+
+    /// Wait until the faucet wallet has caught up with the current chain tip.
+    pub async fn for_wallet_at_tip(&self) -> Result<u64> {
+        let deadline = Instant::now() + self.timeout;
+        let tip = self.until(deadline, "Zebra to answer getblockcount", || block_count(&self.client)).await?;
+        self.wallet_until(tip, deadline).await
+    }
+
+    /// Wait until `txid` is mined and the faucet wallet has synced past it.
+    /// Both steps share one deadline.
+    pub async fn for_confirmed(&self, txid: &str) -> Result<u64> {
+        let deadline = Instant::now() + self.timeout;
+        let height = self.tx_until(txid, deadline).await?;
+        self.wallet_until(height, deadline).await?;
+        Ok(height)
+    }
+
+    async fn tx_until(&self, txid: &str, deadline: Instant) -> Result<u64> {
+        let what = format!("transaction {} to be mined", txid);
+        self.poll(deadline, &what, || async {
+            match tx_state(&self.client, txid).await? {
+                TxState::Mined(height) => Ok(Poll::Ready(height)),
+                TxState::InMempool if self.mine => {
+                    if let Err(e) = mine_block(&self.client).await {
+                        return Ok(Poll::Pending(format!("in mempool; generate failed: {}", e)));
+                    }
+                    Ok(Poll::Pending("in mempool; mined a block".to_string()))
+                }
+                TxState::InMempool => Ok(Poll::Pending("in mempool".to_string())),
+                TxState::Unknown => Ok(Poll::Pending("not in mempool or chain".to_string())),
+            }
+        })
+        .await
+    }
+
+    async fn wallet_until(&self, height: u64, deadline: Instant) -> Result<u64> {
+        let what = format!("faucet wallet to sync to height {}", height);
+        self.poll(deadline, &what, || async {
+            let status = faucet_sync_status(&self.client).await?;
+            let wallet_height = status["wallet_height"].as_u64();
+
+            if let Some(current) = wallet_height.filter(|h| *h >= height) {
+                return Ok(Poll::Ready(current));
+            }
+            if status["running"] != true {
+                start_sync(&self.client).await?;
+            }
+
+            let mut seen = match wallet_height {
+                Some(current) => format!("wallet height {}", current),
+                None => "wallet height unknown".to_string(),
+            };
+            if let Some(error) = status["last_error"].as_str() {
+                seen.push_str(&format!("; last sync error: {}", error));
+            }
+            Ok(Poll::Pending(seen))
+        })
+        .await
+    }
+
+    /// Retry a fallible call until it succeeds.
+    async fn until<T, F, Fut>(&self, deadline: Instant, what: &str, mut call: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.poll(deadline, what, || {
+            let fut = call();
+            async move { fut.await.map(Poll::Ready) }
+        })
+        .await
+    }
+
+    /// Run `check` every interval until it is ready. Errors (e.g. a service
+    /// restarting) are treated like a pending state and retried.
+    async fn poll<T, F, Fut>(&self, deadline: Instant, what: &str, mut check: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<Poll<T>>>,
+    {
+        let start = Instant::now();
+
+        loop {
+            let last_seen = match check().await {
+                Ok(Poll::Ready(value)) => return Ok(value),
+                Ok(Poll::Pending(seen)) => seen,
+                Err(e) => e.to_string(),
+            };
+
+            if Instant::now() + self.interval > deadline {
+                return Err(ZecKitError::WaitTimeout {
+                    what: what.to_string(),
+                    secs: start.elapsed().as_secs(),
+                    last_seen,
+                });
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
+
+enum Poll<T> {
+    Ready(T),
+    /// Not there yet; describes what was seen instead.
+    Pending(String),
+}
+
+async fn zebra_rpc(client: &Client, method: &str, params: Value) -> Result<Value> {
+    let resp = client
+        .post(ZEBRA_RPC)
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params
+        }))
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await?;
+
+    let json: Value = resp.json().await?;
+    if !json["error"].is_null() {
+        return Err(ZecKitError::HealthCheck(format!("{} failed: {}", method, json["error"])));
+    }
+
+    Ok(json["result"].clone())
+}
+
+async fn block_count(client: &Client) -> Result<u64> {
+    zebra_rpc(client, "getblockcount", json!([]))
+        .await?
+        .as_u64()
+        .ok_or_else(|| ZecKitError::HealthCheck("Invalid block count response".into()))
+}
+
+async fn mine_block(client: &Client) -> Result<()> {
+    zebra_rpc(client, "generate", json!([1])).await?;
+    Ok(())
+}
+
+/// Look the transaction up in the mempool first, then in the chain.
+pub async fn tx_state(client: &Client, txid: &str) -> Result<TxState> {
+    let mempool = zebra_rpc(client, "getrawmempool", json!([])).await?;
+    if in_mempool(&mempool, txid) {
+        return Ok(TxState::InMempool);
+    }
+
+    // Zebra answers an error for transactions it has never seen
+    match zebra_rpc(client, "getrawtransaction", json!([txid, 1])).await {
+        Ok(tx) => Ok(mined_state(&tx)),
+        Err(_) => Ok(TxState::Unknown),
+    }
+}
+
+fn in_mempool(mempool: &Value, txid: &str) -> bool {
+    mempool
+        .as_array()
+        .is_some_and(|txids| txids.iter().any(|t| t.as_str() == Some(txid)))
+}
+
+fn mined_state(tx: &Value) -> TxState {
+    match tx["height"].as_u64() {
+        Some(height) if tx["confirmations"].as_u64().unwrap_or(1) > 0 => TxState::Mined(height),
+        _ => TxState::InMempool,
+    }
+}
+
+async fn faucet_sync_status(client: &Client) -> Result<Value> {
+    let resp = client
+        .get(format!("{}/sync/status", FAUCET_API))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck(format!("/sync/status returned {}", resp.status())));
+    }
+
+    Ok(resp.json().await?)
+}
+
+/// Kick off a background sync; `already_running` is fine too.
+async fn start_sync(client: &Client) -> Result<()> {
+    let resp = client
+        .post(format!("{}/sync?wait=false", FAUCET_API))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(ZecKitError::HealthCheck(format!("Starting a wallet sync returned {}", resp.status())));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_transactions() {
+        assert!(in_mempool(&json!(["aa", "bb"]), "bb"));
+        assert!(!in_mempool(&json!([]), "bb"));
+
+        assert_eq!(mined_state(&json!({ "height": 120, "confirmations": 3 })), TxState::Mined(120));
+        assert_eq!(mined_state(&json!({ "hex": "00" })), TxState::InMempool);
+    }
+
+    #[test]
+    fn timeout_error_names_the_wait_and_last_state() {
+        let err = ZecKitError::WaitTimeout {
+            what: "transaction ab to be mined".to_string(),
+            secs: 30,
+            last_seen: "in mempool".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Timed out after 30s waiting for transaction ab to be mined (last seen: in mempool)"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+#!/bin/bash
+# Usage: check-activation-heights.sh <zebra-rpc-host> <zebra-rpc-port>
+#
+# Network upgrade heights come from `zeckit up --activation` (ACTIVATION_NU5
+# ... ACTIVATION_NU7). Zebra is the source of truth and the indexers take
+# the heights from it, so refuse to start when Zebra disagrees with what the
+# devnet was started with.
+set -e
+
+ZEBRA_RPC_HOST=$1
+ZEBRA_RPC_PORT=$2
+
+echo "🔍 Checking network upgrade activation heights..."
+CHAIN_INFO=$(curl -s \
+    -X POST \
+    -H "Content-Type: application/json" \
+    -d '{"jsonrpc":"2.0","id":"info","method":"getblockchaininfo","params":[]}' \
+    "http://${ZEBRA_RPC_HOST}:${ZEBRA_RPC_PORT}" | tr -d ' \n')
+
+MISMATCH=0
+for UPGRADE in "NU5=${ACTIVATION_NU5:-1}" "NU6=${ACTIVATION_NU6:-}" "NU6.1=${ACTIVATION_NU6_1:-}" "NU7=${ACTIVATION_NU7:-}"; do
+    NAME=${UPGRADE%%=*}
+    HEIGHT=${UPGRADE#*=}
+    [ -z "${HEIGHT}" ] && continue
+    if echo "${CHAIN_INFO}" | grep -qF "\"name\":\"${NAME}\",\"activationheight\":${HEIGHT},"; then
+        echo "  ${NAME} activates at ${HEIGHT}"
+    else
+        echo "❌ Zebra does not report ${NAME} at height ${HEIGHT}; check docker/configs/zebra.toml"
+        MISMATCH=1
+    fi
+done
+
+if [ ${MISMATCH} -ne 0 ]; then
+    echo "❌ Activation heights differ from Zebra's; restart the devnet with \`zeckit up --fresh\`"
+    exit 1
+fi
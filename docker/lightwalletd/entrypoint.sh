@@ -58,6 +58,10 @@ done
 
 echo "Zebra has ${BLOCK_COUNT} blocks!"
 
+# Zebra owns the network upgrade heights and this indexer takes them from
+# it; stop here if they differ from what the devnet was started with
+check-activation-heights.sh "${ZEBRA_RPC_HOST}" "${ZEBRA_RPC_PORT}"
+
 # Start lightwalletd with RPC credentials (dummy values for Zebra which doesn't require auth)
 echo "Starting lightwalletd..."
 exec lightwalletd \
@@ -70,10 +70,15 @@ done
 
 echo "Zebra has ${BLOCK_COUNT} blocks!"
 
+# Zebra owns the network upgrade heights and this indexer takes them from
+# it; stop here if they differ from what the devnet was started with
+check-activation-heights.sh "${ZEBRA_RPC_HOST}" "${ZEBRA_RPC_PORT}"
+
 # Create config directory
 mkdir -p ${ZAINO_DATA_DIR}/zainod
 
-# Create Zaino config file with JSONRPC backend
+# Create Zaino config file with JSONRPC backend. This zainod build has no
+# activation height settings; it uses the ones checked against Zebra above
 echo "📝 Creating Zaino config file..."
 echo "# Zaino Configuration - JSONRPC Backend" > ${ZAINO_DATA_DIR}/zainod/zindexer.toml
 echo "network = \"Regtest\"" >> ${ZAINO_DATA_DIR}/zainod/zindexer.toml
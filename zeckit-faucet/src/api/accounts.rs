@@ -0,0 +1,169 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::AppState;
+use crate::error::FaucetError;
+use crate::wallet::FaucetWallet;
+
+/// Header that pins a request to the account registered with that key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Header carrying `ADMIN_API_KEY`, required to create accounts.
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+#[derive(Debug, Deserialize)]
+pub struct CreateAccountRequest {
+    name: String,
+    api_key: Option<String>,
+}
+
+/// Pick the ZIP-32 account a request spends from.
+///
+/// An `X-Api-Key` header pins the request to its tenant's account; an explicit
+/// `account` (name or index) naming a different account is rejected, as is
+/// selecting a key-bound account without its key. Without either, the
+/// faucet's default account is used.
+pub(crate) fn resolve_account(
+    wallet: &dyn FaucetWallet,
+    selector: Option<&str>,
+    headers: &HeaderMap,
+) -> Result<zip32::AccountId, FaucetError> {
+    let accounts = wallet.accounts();
+
+    let from_key = match headers.get(API_KEY_HEADER) {
+        Some(value) => {
+            let key = value.to_str()
+                .map_err(|_| FaucetError::Forbidden("Malformed API key".to_string()))?;
+            Some(accounts.find_by_api_key(key)
+                .ok_or_else(|| FaucetError::Forbidden("Unknown API key".to_string()))?)
+        }
+        None => None,
+    };
+
+    let from_selector = selector
+        .map(|selector| accounts.find(selector)
+            .ok_or_else(|| FaucetError::Validation(format!("Unknown account: {}", selector))))
+        .transpose()?;
+
+    let index = match (from_key, from_selector) {
+        (Some(key), Some(selected)) if key != selected => {
+            return Err(FaucetError::Forbidden(format!(
+                "API key is not valid for account {}",
+                accounts.name_of(selected)
+            )));
+        }
+        (None, Some(selected)) if accounts.requires_api_key(selected) => {
+            return Err(FaucetError::Forbidden(format!(
+                "Account {} requires its API key",
+                accounts.name_of(selected)
+            )));
+        }
+        (Some(index), _) | (None, Some(index)) => index,
+        (None, None) => 0,
+    };
+
+    zip32::AccountId::try_from(index)
+        .map_err(|_| FaucetError::Validation(format!("Invalid account index: {}", index)))
+}
+
+/// GET /accounts - List the faucet's accounts with their addresses and balances
+pub(crate) async fn list_accounts(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let wallet = state.wallet.read().await;
+
+    let mut entries = vec![(0u32, wallet.accounts().name_of(0), None)];
+    entries.extend(wallet.accounts().get_all().iter()
+        .map(|a| (a.index, a.name.clone(), Some(a.created_at.to_rfc3339()))));
+
+    let mut accounts = Vec::with_capacity(entries.len());
+    for (index, name, created_at) in entries {
+        let account = zip32::AccountId::try_from(index)
+            .map_err(|_| FaucetError::Internal(format!("Invalid account index: {}", index)))?;
+        let address = wallet.get_account_address(account).await.ok();
+        let balance = wallet.get_account_balance(account).await.ok();
+
+        accounts.push(json!({
+            "name": name,
+            "index": index,
+            "unified_address": address,
+            "balance": balance.as_ref().map(|b| b.total_zec()),
+            "orchard_balance": balance.as_ref().map(|b| b.orchard_zec()),
+            "created_at": created_at,
+        }));
+    }
+
+    Ok(Json(json!({
+        "count": accounts.len(),
+        "accounts": accounts
+    })))
+}
+
+/// POST /accounts - Add a named sub-account derived from the faucet seed
+///
+/// Requires `X-Admin-Key` to match `ADMIN_API_KEY`; without one configured,
+/// account creation is disabled. The account's keys are added to the running
+/// wallet, so existing balances are untouched and nothing is rescanned.
+/// Fund the account by sending to its unified address from `/send`.
+pub(crate) async fn create_account(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateAccountRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), FaucetError> {
+    let admin_key = state.config.admin_api_key.as_deref().ok_or_else(|| {
+        FaucetError::Forbidden(
+            "Account creation is disabled; set ADMIN_API_KEY to enable it".to_string(),
+        )
+    })?;
+    let provided = headers.get(ADMIN_KEY_HEADER).and_then(|value| value.to_str().ok());
+    if provided != Some(admin_key) {
+        return Err(FaucetError::Forbidden("Missing or invalid admin key".to_string()));
+    }
+
+    let mut wallet = state.wallet.write().await;
+    let record = wallet.create_account(payload.name.trim(), payload.api_key).await?;
+    let account = zip32::AccountId::try_from(record.index)
+        .map_err(|_| FaucetError::Internal(format!("Invalid account index: {}", record.index)))?;
+    let address = wallet.get_account_address(account).await?;
+
+    Ok((StatusCode::CREATED, Json(json!({
+        "status": "created",
+        "name": record.name,
+        "index": record.index,
+        "unified_address": address,
+        "api_key_set": record.api_key.is_some(),
+        "created_at": record.created_at.to_rfc3339(),
+    }))))
+}
+
+/// GET /accounts/:name/balance - Balance of one account (name or index)
+///
+/// Key-bound accounts need their `X-Api-Key`, as on `/request` and `/send`.
+pub(crate) async fn get_account_balance(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let wallet = state.wallet.read().await;
+
+    let account = resolve_account(&*wallet, Some(&name), &headers)?;
+    let index = u32::from(account);
+
+    let address = wallet.get_account_address(account).await?;
+    let balance = wallet.get_account_balance(account).await?;
+
+    Ok(Json(json!({
+        "name": wallet.accounts().name_of(index),
+        "index": index,
+        "unified_address": address,
+        "transparent_balance": balance.transparent_zec(),
+        "sapling_balance": balance.sapling_zec(),
+        "orchard_balance": balance.orchard_zec(),
+        "total_balance": balance.total_zec()
+    })))
+}
@@ -0,0 +1,145 @@
+use axum::{extract::{Query, State}, http::StatusCode, Json};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::time::Duration;
+
+use crate::AppState;
+use crate::error::FaucetError;
+use crate::wallet::backend;
+use crate::wallet::sync::{finish_rescan, start_rescan};
+
+/// Rescans from genesis can take a while on a long-running devnet.
+const RESCAN_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Deserialize)]
+pub struct RescanQuery {
+    from_height: Option<u32>,
+}
+
+/// POST /admin/rescan?from_height=N - Rebuild the wallet from its seed and rescan
+///
+/// Fixes a desynced wallet after a chain reset without `zeckit down --purge`.
+/// The rescan runs in the background; `/sync/status` reports it under
+/// `rescan` with the `rescan_id` returned here.
+pub(crate) async fn rescan(
+    State(state): State<AppState>,
+    Query(params): Query<RescanQuery>,
+) -> Result<(StatusCode, Json<serde_json::Value>), FaucetError> {
+    let from_height = params.from_height.unwrap_or(0);
+    let rescan_id = rebuild_and_rescan(state, from_height, false).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(json!({
+        "status": "rescanning",
+        "rescan_id": rescan_id,
+        "from_height": from_height,
+        "message": format!("Wallet rebuilt from seed; rescanning from height {}", from_height)
+    }))))
+}
+
+/// POST /admin/reset-wallet - Rebuild the wallet from its seed and clear history
+pub(crate) async fn reset_wallet(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<serde_json::Value>), FaucetError> {
+    let rescan_id = rebuild_and_rescan(state, 0, true).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(json!({
+        "status": "reset",
+        "rescan_id": rescan_id,
+        "from_height": 0,
+        "message": "Wallet and history reset; rescanning from genesis"
+    }))))
+}
+
+/// Rebuild the wallet and start its rescan in the background. Returns the
+/// rescan id.
+async fn rebuild_and_rescan(
+    state: AppState,
+    from_height: u32,
+    clear_history: bool,
+) -> Result<u64, FaucetError> {
+    let id = {
+        let mut wallet = state.wallet.write().await;
+        wallet.rebuild_from_seed(from_height, clear_history).await?;
+        start_rescan(&state.sync, from_height).await
+    };
+
+    tokio::spawn(async move {
+        let mut wallet = state.wallet.write().await;
+        match finish_rescan(&mut *wallet, &state.sync, id, RESCAN_TIMEOUT).await {
+            Ok(()) => tracing::info!("✓ Rescan #{} from height {} complete", id, from_height),
+            Err(e) => tracing::warn!("⚠ Rescan #{} from height {} failed: {}", id, from_height, e),
+        }
+    });
+
+    Ok(id)
+}
+
+/// GET /admin/backend - Configured indexers and the one in use
+pub(crate) async fn get_backend(
+    State(state): State<AppState>,
+) -> Json<serde_json::Value> {
+    let backends = state.backends.read().await.clone();
+    Json(json!(backends))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetBackendRequest {
+    /// A configured indexer's URI, host name or kind (`zaino`, `lightwalletd`).
+    backend: String,
+    from_height: Option<u32>,
+}
+
+/// POST /admin/backend - Switch the wallet to another configured indexer
+///
+/// The wallet is rebuilt from its seed and rescans through the new indexer
+/// (from genesis unless `from_height` is given), so balances afterwards
+/// reflect only what that indexer served. Follow the rescan on `/sync/status`
+/// by its `rescan_id`.
+pub(crate) async fn set_backend(
+    State(state): State<AppState>,
+    Json(request): Json<SetBackendRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), FaucetError> {
+    let indexer = {
+        let backends = state.backends.read().await;
+        backends.resolve(&request.backend).cloned().ok_or_else(|| {
+            let configured: Vec<_> = backends.indexers.iter().map(|i| i.uri.as_str()).collect();
+            FaucetError::Validation(format!(
+                "Unknown backend {:?}; configured: {}",
+                request.backend,
+                configured.join(", ")
+            ))
+        })?
+    };
+
+    // Don't trade a working wallet for an indexer that isn't there
+    backend::probe(&indexer).await?;
+
+    let from_height = request.from_height.unwrap_or(0);
+    let id = {
+        let mut wallet = state.wallet.write().await;
+        let reason = "requested via /admin/backend";
+        backend::switch(&mut *wallet, &state.sync, &state.backends, &indexer, from_height, reason).await?;
+        start_rescan(&state.sync, from_height).await
+    };
+
+    let response = json!({
+        "status": "switching",
+        "rescan_id": id,
+        "backend": indexer,
+        "from_height": from_height,
+        "message": format!(
+            "Wallet rebuilt from seed; rescanning through {} from height {}",
+            indexer.kind.name(), from_height
+        )
+    });
+
+    tokio::spawn(async move {
+        let mut wallet = state.wallet.write().await;
+        match finish_rescan(&mut *wallet, &state.sync, id, RESCAN_TIMEOUT).await {
+            Ok(()) => tracing::info!("✓ Rescan through {} complete", indexer.uri),
+            Err(e) => tracing::warn!("⚠ Rescan through {} failed: {}", indexer.uri, e),
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
@@ -1,15 +1,19 @@
-use axum::{Json, extract::State};
+use axum::{Json, extract::State, http::HeaderMap};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use zcash_address::ZcashAddress;
 use crate::AppState;
 use crate::error::FaucetError;
+use crate::api::accounts::resolve_account;
+use crate::wallet::memo::MemoInput;
 
 #[derive(Debug, Deserialize)]
 pub struct FaucetRequest {
     address: String,
     amount: Option<f64>,
-    memo: Option<String>,
+    memo: Option<MemoInput>,
+    /// Account name or index to pay from; defaults to the `X-Api-Key` account.
+    account: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -43,6 +47,7 @@ fn validate_address(address: &str) -> Result<String, FaucetError> {
 /// This handler is exposed via routing but not part of the public module API.
 pub(crate) async fn request_funds(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> Result<Json<FaucetResponse>, FaucetError> {
     // Validate address
@@ -58,12 +63,15 @@ pub(crate) async fn request_funds(
         )));
     }
     
+    let memo_bytes = payload.memo.as_ref().map(MemoInput::to_memo_bytes).transpose()?;
+
     // Send transaction
     let mut wallet = state.wallet.write().await;
-    let txid = wallet.send_transaction(&validated_address, amount, payload.memo).await?;
+    let account = resolve_account(&*wallet, payload.account.as_deref(), &headers)?;
+    let txid = wallet.send_transaction(&validated_address, amount, memo_bytes, account).await?;
     
     // Get new balance
-    let new_balance = wallet.get_balance().await?;
+    let new_balance = wallet.get_account_balance(account).await?;
     
     Ok(Json(FaucetResponse {
         success: true,
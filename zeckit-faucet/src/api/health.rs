@@ -1,21 +1,192 @@
-use axum::{Json, extract::State};
-use serde_json::json;
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
 
 use crate::AppState;
 use crate::error::FaucetError;
+use crate::wallet::backend;
 
 pub(crate) async fn health_check(
     State(state): State<AppState>,
 ) -> Result<Json<serde_json::Value>, FaucetError> {
+    // Probe before taking the wallet lock; an unreachable indexer takes a
+    // few seconds to time out
+    let indexer = state.backends.read().await.active.clone();
+    let indexer = match backend::probe(&indexer).await {
+        Ok(info) => json!({
+            "kind": indexer.kind,
+            "uri": indexer.uri,
+            "vendor": info.vendor,
+            "version": info.version,
+            "height": info.height,
+        }),
+        Err(e) => json!({
+            "kind": indexer.kind,
+            "uri": indexer.uri,
+            "error": e.to_string(),
+        }),
+    };
+
     let wallet = state.wallet.read().await;
     let balance = wallet.get_balance().await?;
+    let refill = state.refill.read().await.clone();
 
     Ok(Json(json!({
         "status": "healthy",
         "wallet_backend": "zingolib",
+        "indexer": indexer,
         "network": "regtest",
         "balance": balance.total_zec(),
+        "orchard_balance": balance.orchard_zec(),
+        "refill": refill,
         "timestamp": chrono::Utc::now().to_rfc3339(),
         "version": "0.3.0"
     })))
-}
\ No newline at end of file
+}
+/// GET /livez - The process is up and serving requests
+///
+/// Takes no locks and calls nothing, so it answers even while a sync holds
+/// the wallet.
+pub(crate) async fn livez(State(state): State<AppState>) -> Json<Value> {
+    Json(json!({
+        "status": "alive",
+        "uptime_secs": (Utc::now() - state.start_time).num_seconds(),
+        "version": "0.3.0"
+    }))
+}
+
+/// GET /readyz - The faucet can actually serve funds
+///
+/// Checks indexer reachability, wallet sync lag, spendable Orchard balance
+/// and the age of the last successful sync, each reported as its own
+/// component. Answers 503 unless every component is ok. Takes no wallet lock.
+pub(crate) async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    let sync = state.sync.read().await.clone();
+    let active = state.backends.read().await.active.clone();
+    let (probe, chain_height) = tokio::join!(backend::probe(&active), state.zebra.get_block_count());
+    let config = &state.config;
+
+    let indexer = match probe {
+        Ok(info) => component(true, json!({
+            "kind": active.kind,
+            "uri": active.uri,
+            "version": info.version,
+            "height": info.height,
+        })),
+        Err(e) => component(false, json!({
+            "kind": active.kind,
+            "uri": active.uri,
+            "error": e.to_string(),
+        })),
+    };
+
+    let components = json!({
+        "indexer": indexer,
+        "sync": sync_component(
+            sync.wallet_height,
+            chain_height.map_err(|e| e.to_string()),
+            config.ready_max_blocks_behind,
+        ),
+        "balance": balance_component(
+            sync.orchard_zatoshis,
+            (config.ready_min_balance * 100_000_000.0) as u64,
+        ),
+        "last_sync": last_sync_component(sync.last_sync, Utc::now(), config.ready_max_sync_age_secs),
+    });
+
+    let ready = components
+        .as_object()
+        .is_some_and(|c| c.values().all(|component| component["status"] == "ok"));
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    (status, Json(json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "components": components,
+        "timestamp": Utc::now().to_rfc3339(),
+    })))
+}
+
+fn component(ok: bool, mut detail: Value) -> Value {
+    detail["status"] = json!(if ok { "ok" } else { "fail" });
+    detail
+}
+
+fn sync_component(wallet_height: Option<u64>, chain_height: Result<u64, String>, max_behind: u64) -> Value {
+    match (wallet_height, chain_height) {
+        (_, Err(e)) => component(false, json!({ "wallet_height": wallet_height, "error": e })),
+        (None, Ok(chain)) => component(false, json!({
+            "wallet_height": null,
+            "chain_height": chain,
+            "error": "wallet has not completed a sync yet",
+        })),
+        (Some(wallet), Ok(chain)) => {
+            let behind = chain.saturating_sub(wallet);
+            component(behind <= max_behind, json!({
+                "wallet_height": wallet,
+                "chain_height": chain,
+                "blocks_behind": behind,
+                "max_blocks_behind": max_behind,
+            }))
+        }
+    }
+}
+
+fn balance_component(orchard_zatoshis: Option<u64>, min_zatoshis: u64) -> Value {
+    let zec = |z: u64| z as f64 / 100_000_000.0;
+    match orchard_zatoshis {
+        Some(orchard) => component(orchard >= min_zatoshis, json!({
+            "orchard_balance": zec(orchard),
+            "min_balance": zec(min_zatoshis),
+        })),
+        None => component(false, json!({
+            "orchard_balance": null,
+            "min_balance": zec(min_zatoshis),
+            "error": "balance unknown until a sync completes",
+        })),
+    }
+}
+
+fn last_sync_component(last_sync: Option<DateTime<Utc>>, now: DateTime<Utc>, max_age_secs: u64) -> Value {
+    match last_sync {
+        Some(at) => {
+            let age = (now - at).num_seconds().max(0) as u64;
+            component(age <= max_age_secs, json!({
+                "last_sync": at.to_rfc3339(),
+                "age_secs": age,
+                "max_age_secs": max_age_secs,
+            }))
+        }
+        None => component(false, json!({
+            "last_sync": null,
+            "max_age_secs": max_age_secs,
+            "error": "no successful sync yet",
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_component_allows_small_lag() {
+        assert_eq!(sync_component(Some(95), Ok(100), 10)["status"], "ok");
+        assert_eq!(sync_component(Some(80), Ok(100), 10)["status"], "fail");
+        assert_eq!(sync_component(None, Ok(100), 10)["status"], "fail");
+        assert_eq!(sync_component(Some(100), Err("down".into()), 10)["status"], "fail");
+    }
+
+    #[test]
+    fn test_balance_and_last_sync_components() {
+        assert_eq!(balance_component(Some(200_000_000), 100_000_000)["status"], "ok");
+        assert_eq!(balance_component(Some(50_000_000), 100_000_000)["status"], "fail");
+        assert_eq!(balance_component(None, 0)["status"], "fail");
+
+        let now = Utc::now();
+        let recent = now - chrono::Duration::seconds(30);
+        let stale = now - chrono::Duration::seconds(600);
+        assert_eq!(last_sync_component(Some(recent), now, 300)["status"], "ok");
+        assert_eq!(last_sync_component(Some(stale), now, 300)["status"], "fail");
+        assert_eq!(last_sync_component(None, now, 300)["status"], "fail");
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{extract::{Query, State}, http::HeaderMap, Json};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::AppState;
+use crate::error::FaucetError;
+use crate::api::accounts::resolve_account;
+
+#[derive(Debug, Deserialize)]
+pub struct KeysQuery {
+    account: Option<String>,
+}
+
+/// GET /keys/viewing - Unified full/incoming viewing keys and wallet birthday
+///
+/// Lets a wallet under development import the faucet's funds view-only.
+pub(crate) async fn get_viewing_keys(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<KeysQuery>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let wallet = state.wallet.read().await;
+    let account = resolve_account(&*wallet, params.account.as_deref(), &headers)?;
+
+    let (ufvk, uivk) = wallet.viewing_keys(account).await?;
+    let birthday = wallet.birthday().await;
+
+    Ok(Json(json!({
+        "account": wallet.accounts().name_of(u32::from(account)),
+        "account_index": u32::from(account),
+        "ufvk": ufvk,
+        "uivk": uivk,
+        "birthday": birthday,
+        "network": "regtest"
+    })))
+}
+
+/// GET /keys/seed - The wallet mnemonic (only with `KEYS_SEED_ENABLED=true`)
+///
+/// Off by default: anyone who can reach the faucet could spend all of its
+/// funds, which is acceptable on a private regtest devnet only.
+pub(crate) async fn get_seed(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    if !state.config.keys_seed_enabled {
+        return Err(FaucetError::Forbidden(
+            "Seed export is disabled; set KEYS_SEED_ENABLED=true to enable it".to_string()
+        ));
+    }
+
+    let wallet = state.wallet.read().await;
+    let seed = wallet.seed_phrase()
+        .ok_or_else(|| FaucetError::Wallet("No stored wallet seed".to_string()))?;
+
+    Ok(Json(json!({
+        "seed": seed,
+        "birthday": wallet.birthday().await,
+        "accounts": wallet.accounts().account_count(),
+        "network": "regtest"
+    })))
+}
@@ -1,7 +1,11 @@
+pub mod accounts;
+pub mod admin;
 pub mod health;
+pub mod keys;
 pub mod faucet;
 pub mod stats;
 pub mod wallet;
+pub mod wallets;
 
 use axum::{Json, extract::State};
 use serde_json::json;
@@ -17,13 +21,24 @@ pub(crate) async fn root(State(_state): State<AppState>) -> Json<serde_json::Val
         "wallet_backend": "zingolib",
         "endpoints": {
             "health": "/health",
+            "livez": "/livez",
+            "readyz": "/readyz",
             "stats": "/stats",
             "request": "/request",
             "address": "/address",
+            "address_new": "/address/new",
+            "addresses": "/addresses",
             "sync": "/sync",
+            "sync_status": "/sync/status",
             "shield": "/shield",
             "send": "/send",
-            "history": "/history"
+            "history": "/history",
+            "accounts": "/accounts",
+            "viewing_keys": "/keys/viewing",
+            "ephemeral_wallet": "/wallets/ephemeral",
+            "admin_rescan": "/admin/rescan",
+            "admin_reset_wallet": "/admin/reset-wallet",
+            "admin_backend": "/admin/backend"
         }
     }))
 }
\ No newline at end of file
@@ -1,13 +1,15 @@
-use axum::{Json, extract::{State, Query}};
+use axum::{Json, extract::{State, Query}, http::HeaderMap};
 use serde::Deserialize;
 use serde_json::json;
 
 use crate::AppState;
 use crate::error::FaucetError;
+use crate::api::accounts::{resolve_account, API_KEY_HEADER};
 
 #[derive(Debug, Deserialize)]
 pub struct HistoryQuery {
     limit: Option<usize>,
+    account: Option<String>,
 }
 
 pub(crate) async fn get_stats(
@@ -22,7 +24,7 @@ pub(crate) async fn get_stats(
     let uptime = chrono::Utc::now() - state.start_time;
     let uptime_seconds = uptime.num_seconds();
 
-    let recent_txs = wallet.get_transaction_history(5);
+    let recent_txs = wallet.get_transaction_history(5, None);
     let last_request = recent_txs.first().map(|tx| tx.timestamp.to_rfc3339());
 
     Ok(Json(json!({
@@ -42,16 +44,25 @@ pub(crate) async fn get_stats(
 
 pub(crate) async fn get_history(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Query(params): Query<HistoryQuery>,
 ) -> Result<Json<serde_json::Value>, FaucetError> {
     let wallet = state.wallet.read().await;
     
     let limit = params.limit.unwrap_or(100).min(1000).max(1);
-    let history = wallet.get_transaction_history(limit);
+
+    // Unscoped requests see everything; a selector or API key narrows to one account
+    let account = if params.account.is_some() || headers.contains_key(API_KEY_HEADER) {
+        Some(u32::from(resolve_account(&*wallet, params.account.as_deref(), &headers)?))
+    } else {
+        None
+    };
+    let history = wallet.get_transaction_history(limit, account);
 
     Ok(Json(json!({
         "count": history.len(),
         "limit": limit,
+        "account": account.map(|index| wallet.accounts().name_of(index)),
         "transactions": history
     })))
 }
\ No newline at end of file
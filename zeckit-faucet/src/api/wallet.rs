@@ -1,8 +1,15 @@
-use axum::{extract::State, Json};
+use axum::{extract::{Query, State}, http::{HeaderMap, StatusCode}, Json};
 use serde::Deserialize;
 use serde_json::json;
+use tokio::time::Duration;
 use zcash_protocol::value::Zatoshis;
 use crate::{AppState, error::FaucetError};
+use crate::wallet::sync::tracked_sync;
+use crate::api::accounts::resolve_account;
+use crate::wallet::addresses::Receivers;
+use crate::wallet::memo::{self, MemoInput};
+
+const SYNC_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// GET /address - Returns wallet addresses
 pub(crate) async fn get_addresses(
@@ -19,17 +26,158 @@ pub(crate) async fn get_addresses(
     })))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct NewAddressQuery {
+    receivers: Option<String>,
+    account: Option<String>,
+}
+
+/// POST /address/new?receivers=orchard,sapling,transparent - Derive a fresh address
+///
+/// Shielded receivers go into a new diversified unified address; `transparent`
+/// additionally derives a new transparent address. A Sapling-only request also
+/// returns the bare Sapling address. Defaults to orchard,sapling.
+pub(crate) async fn new_address(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<NewAddressQuery>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let receivers = match params.receivers.as_deref() {
+        Some(list) => Receivers::parse(list)?,
+        None => Receivers::default(),
+    };
+
+    let mut wallet = state.wallet.write().await;
+    let account = resolve_account(&*wallet, params.account.as_deref(), &headers)?;
+    let derived = wallet.new_address(receivers, account).await?;
+
+    Ok(Json(json!({
+        "account": wallet.accounts().name_of(u32::from(account)),
+        "receivers": receivers.names(),
+        "addresses": derived
+    })))
+}
+
+/// GET /addresses - Every address derived via `/address/new` with the amount received on it
+pub(crate) async fn list_addresses(
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let wallet = state.wallet.read().await;
+    let received = wallet.received_by_address().await;
+
+    let addresses: Vec<_> = wallet.derived_addresses()
+        .iter()
+        .map(|derived| {
+            let zatoshis = received.get(&derived.address).copied().unwrap_or(0);
+            json!({
+                "address": derived.address,
+                "kind": derived.kind,
+                "receivers": derived.receivers,
+                "account": derived.account,
+                "created_at": derived.created_at.to_rfc3339(),
+                "received_zatoshis": zatoshis,
+                "received": zatoshis as f64 / 100_000_000.0
+            })
+        })
+        .collect();
+
+    Ok(Json(json!({
+        "count": addresses.len(),
+        "addresses": addresses
+    })))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncQuery {
+    wait: Option<bool>,
+}
+
 /// POST /sync - Syncs wallet with blockchain
+///
+/// Blocks until the sync finishes unless called with `?wait=false`, in which
+/// case the sync runs in the background and progress is visible on `/sync/status`.
 pub(crate) async fn sync_wallet(
     State(state): State<AppState>,
+    Query(params): Query<SyncQuery>,
+) -> Result<(StatusCode, Json<serde_json::Value>), FaucetError> {
+    if params.wait.unwrap_or(true) {
+        let mut wallet = state.wallet.write().await;
+        tracked_sync(&mut *wallet, &state.sync, SYNC_TIMEOUT).await?;
+
+        return Ok((StatusCode::OK, Json(json!({
+            "status": "synced",
+            "message": "Wallet synced with blockchain"
+        }))));
+    }
+
+    // Check and claim under one lock, so concurrent calls start one sync and
+    // `/sync/status` shows it running before the task gets the wallet
+    {
+        let mut sync = state.sync.write().await;
+        if sync.running {
+            return Ok((StatusCode::ACCEPTED, Json(json!({
+                "status": "already_running",
+                "message": "A wallet sync is already in progress"
+            }))));
+        }
+        sync.running = true;
+    }
+
+    // tracked_sync clears `running` when it finishes
+    tokio::spawn(async move {
+        let mut wallet = state.wallet.write().await;
+        if let Err(e) = tracked_sync(&mut *wallet, &state.sync, SYNC_TIMEOUT).await {
+            tracing::warn!("⚠ Background sync via /sync failed: {}", e);
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(json!({
+        "status": "started",
+        "message": "Wallet sync started; poll /sync/status for progress"
+    }))))
+}
+
+/// GET /sync/status - Reports wallet sync progress against the chain tip
+///
+/// Never waits on the wallet lock: while a sync holds it, the height from
+/// the last completed sync is reported.
+pub(crate) async fn sync_status(
+    State(state): State<AppState>,
 ) -> Result<Json<serde_json::Value>, FaucetError> {
-    let mut wallet = state.wallet.write().await;
-    
-    wallet.sync().await?;
-    
+    let status = state.sync.read().await.clone();
+
+    let wallet_height = match state.wallet.try_read() {
+        Ok(wallet) => wallet.synced_height().await.or(status.wallet_height),
+        Err(_) => status.wallet_height,
+    };
+
+    let (chain_height, chain_error) = match state.zebra.get_block_count().await {
+        Ok(height) => (Some(height), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    let percent = match (wallet_height, chain_height) {
+        (Some(wallet), Some(chain)) if chain > 0 => {
+            Some((wallet as f64 / chain as f64 * 100.0).min(100.0))
+        }
+        _ => None,
+    };
+    let blocks_behind = match (wallet_height, chain_height) {
+        (Some(wallet), Some(chain)) => Some(chain.saturating_sub(wallet)),
+        _ => None,
+    };
+
     Ok(Json(json!({
-        "status": "synced",
-        "message": "Wallet synced with blockchain"
+        "running": status.running,
+        "wallet_height": wallet_height,
+        "chain_height": chain_height,
+        "blocks_behind": blocks_behind,
+        "percent": percent,
+        "sync_count": status.sync_count,
+        "last_sync": status.last_sync.map(|t| t.to_rfc3339()),
+        "last_error": status.last_error,
+        "rescan": status.rescan,
+        "chain_error": chain_error
     })))
 }
 
@@ -79,18 +227,24 @@ pub(crate) async fn shield_funds(
 pub struct SendRequest {
     pub address: String,
     pub amount: f64,
-    pub memo: Option<String>,
+    pub memo: Option<MemoInput>,
+    /// Account name or index to spend from; defaults to the `X-Api-Key` account.
+    pub account: Option<String>,
 }
 
 /// POST /send - Send shielded funds to another address
 /// This performs a shielded send from Orchard pool to recipient's address
 pub(crate) async fn send_shielded(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<SendRequest>,
 ) -> Result<Json<serde_json::Value>, FaucetError> {
+    let memo_bytes = payload.memo.as_ref().map(MemoInput::to_memo_bytes).transpose()?;
+
     let mut wallet = state.wallet.write().await;
+    let account = resolve_account(&*wallet, payload.account.as_deref(), &headers)?;
     
-    let balance = wallet.get_balance().await?;
+    let balance = wallet.get_account_balance(account).await?;
     
     // Check if we have enough in Orchard pool
     let amount_zatoshis = Zatoshis::from_u64((payload.amount * 100_000_000.0) as u64)
@@ -107,17 +261,21 @@ pub(crate) async fn send_shielded(
     let txid = wallet.send_transaction(
         &payload.address,
         payload.amount,
-        payload.memo.clone(),
+        memo_bytes.clone(),
+        account,
     ).await?;
     
-    let new_balance = wallet.get_balance().await?;
+    let new_balance = wallet.get_account_balance(account).await?;
     
     Ok(Json(json!({
         "status": "sent",
         "txid": txid,
         "to_address": payload.address,
         "amount": payload.amount,
-        "memo": payload.memo.unwrap_or_default(),
+        "account": wallet.accounts().name_of(u32::from(account)),
+        "memo": memo_bytes.as_ref().map(memo::memo_text).unwrap_or_default(),
+        "memo_kind": memo_bytes.as_ref().map(memo::memo_kind),
+        "memo_hex": memo_bytes.as_ref().map(memo::memo_hex),
         "new_balance": new_balance.total_zec(),
         "orchard_balance": new_balance.orchard_zec(),
         "timestamp": chrono::Utc::now().to_rfc3339(),
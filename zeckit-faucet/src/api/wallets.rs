@@ -0,0 +1,125 @@
+use axum::{extract::State, http::HeaderMap, Json};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::AppState;
+use crate::error::FaucetError;
+use crate::api::accounts::resolve_account;
+use crate::wallet::addresses::Receivers;
+
+/// Blocks mined beyond the requested confirmations before giving up on the
+/// funding transaction (e.g. it never reached Zebra's mempool).
+const EXTRA_BLOCKS: u32 = 5;
+
+/// Each confirmation mines a block, so keep requests from tying Zebra up.
+/// Matches coinbase maturity, the deepest confirmation a test usually needs.
+const MAX_CONFIRMATIONS: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct EphemeralRequest {
+    /// ZEC sent to each requested pool.
+    amount: Option<f64>,
+    /// Any of `orchard`, `sapling`, `transparent` (default: orchard).
+    pools: Option<Vec<String>>,
+    confirmations: Option<u32>,
+    account: Option<String>,
+}
+
+/// POST /wallets/ephemeral - Create a throwaway test wallet and fund it
+///
+/// Generates a random mnemonic, funds its addresses in the chosen pools in a
+/// single transaction, and mines until that transaction confirms. The
+/// mnemonic is returned and not kept by the faucet.
+pub(crate) async fn create_ephemeral(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<EphemeralRequest>,
+) -> Result<Json<serde_json::Value>, FaucetError> {
+    let amount = payload.amount.unwrap_or(state.config.faucet_amount_default);
+    if amount < state.config.faucet_amount_min || amount > state.config.faucet_amount_max {
+        return Err(FaucetError::InvalidAmount(format!(
+            "Amount must be between {} and {} ZEC",
+            state.config.faucet_amount_min,
+            state.config.faucet_amount_max
+        )));
+    }
+
+    let pools = match payload.pools {
+        Some(pools) => Receivers::parse(&pools.join(","))?,
+        None => Receivers { orchard: true, sapling: false, transparent: false },
+    };
+    let confirmations = payload.confirmations.unwrap_or(1).max(1);
+    if confirmations > MAX_CONFIRMATIONS {
+        return Err(FaucetError::Validation(format!(
+            "confirmations must be at most {}",
+            MAX_CONFIRMATIONS
+        )));
+    }
+
+    // The limit applies to the whole wallet, not to each pool
+    let total = amount * pools.names().len() as f64;
+    if total > state.config.faucet_amount_max {
+        return Err(FaucetError::InvalidAmount(format!(
+            "{} ZEC to each of {} pools exceeds the {} ZEC maximum",
+            amount,
+            pools.names().len(),
+            state.config.faucet_amount_max
+        )));
+    }
+
+    let birthday = state.zebra.get_block_count().await? as u32;
+
+    let (ephemeral, txid) = {
+        let mut wallet = state.wallet.write().await;
+        let account = resolve_account(&*wallet, payload.account.as_deref(), &headers)?;
+        let ephemeral = wallet.derive_ephemeral(birthday).await?;
+
+        let mut payments = Vec::new();
+        if pools.orchard {
+            payments.push((ephemeral.unified_address.clone(), amount, None));
+        }
+        if pools.sapling {
+            payments.push((ephemeral.sapling_address.clone(), amount, None));
+        }
+        if pools.transparent {
+            payments.push((ephemeral.transparent_address.clone(), amount, None));
+        }
+
+        let txid = wallet.send_payments(payments, account).await?;
+        (ephemeral, txid)
+    };
+
+    // Mine until the funding transaction is confirmed
+    let mut blocks_mined = 0;
+    loop {
+        let current = state.zebra.get_confirmations(&txid).await.unwrap_or(0);
+        if current >= confirmations as u64 {
+            break;
+        }
+        if blocks_mined >= confirmations.saturating_add(EXTRA_BLOCKS) {
+            return Err(FaucetError::TransactionFailed(format!(
+                "Funding transaction {} not confirmed after mining {} blocks",
+                txid, blocks_mined
+            )));
+        }
+        state.zebra.generate(1).await?;
+        blocks_mined += 1;
+    }
+
+    let height = state.zebra.get_block_count().await?;
+
+    Ok(Json(json!({
+        "mnemonic": ephemeral.mnemonic,
+        "birthday": ephemeral.birthday,
+        "unified_address": ephemeral.unified_address,
+        "sapling_address": ephemeral.sapling_address,
+        "transparent_address": ephemeral.transparent_address,
+        "pools": pools.names(),
+        "amount_per_pool": amount,
+        "funding_txids": [txid],
+        "confirmations": confirmations,
+        "blocks_mined": blocks_mined,
+        "height": height,
+        "network": "regtest"
+    })))
+}
@@ -1,14 +1,139 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub zingo_data_dir: PathBuf,
-    pub lightwalletd_uri: String,
+    /// Indexers to sync through, in order of preference.
+    pub indexers: Vec<Indexer>,
+    /// How long startup waits for an indexer to answer.
+    pub indexer_ready_timeout_secs: u64,
     pub zebra_rpc_url: String,
     pub faucet_amount_min: f64,
     pub faucet_amount_max: f64,
     pub faucet_amount_default: f64,
+    pub auto_shield_enabled: bool,
+    pub auto_shield_threshold: f64,
+    pub auto_shield_interval_secs: u64,
+    pub refill_enabled: bool,
+    pub refill_threshold: f64,
+    pub refill_blocks: u32,
+    pub refill_interval_secs: u64,
+    pub keys_seed_enabled: bool,
+    /// Key `POST /accounts` must be called with in `X-Admin-Key`; account
+    /// creation is disabled without one.
+    pub admin_api_key: Option<String>,
+    /// `/readyz` thresholds: wallet lag, spendable Orchard funds (ZEC) and
+    /// time since the last successful sync.
+    pub ready_max_blocks_behind: u64,
+    pub ready_min_balance: f64,
+    pub ready_max_sync_age_secs: u64,
+    /// How long shutdown may take to drain requests and save the wallet.
+    pub shutdown_timeout_secs: u64,
+    pub activation_heights: ActivationHeights,
+}
+
+/// Regtest network upgrade heights, matching the ones `zeckit up` wrote to
+/// Zebra's config. An upgrade without a height never activates.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ActivationHeights {
+    pub nu5: u32,
+    pub nu6: Option<u32>,
+    pub nu6_1: Option<u32>,
+    pub nu7: Option<u32>,
+}
+
+/// Which light-client server implementation an indexer runs. Both speak
+/// the same `CompactTxStreamer` gRPC service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexerKind {
+    Zaino,
+    Lightwalletd,
+}
+
+impl IndexerKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IndexerKind::Zaino => "zaino",
+            IndexerKind::Lightwalletd => "lightwalletd",
+        }
+    }
+}
+
+impl FromStr for IndexerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "zaino" => Ok(IndexerKind::Zaino),
+            "lightwalletd" | "lwd" => Ok(IndexerKind::Lightwalletd),
+            other => Err(anyhow::anyhow!("Unknown indexer kind {:?} (expected zaino or lightwalletd)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Indexer {
+    pub uri: String,
+    pub kind: IndexerKind,
+}
+
+/// Pair `LIGHTWALLETD_URIS` with `INDEXER_KIND`: one kind per URI, or a
+/// single kind for all of them.
+fn indexers(uris: &str, kinds: &str) -> anyhow::Result<Vec<Indexer>> {
+    let split = |list: &str| -> Vec<String> {
+        list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+    };
+    let uris = split(uris);
+    let kinds = split(kinds)
+        .iter()
+        .map(|kind| kind.parse())
+        .collect::<anyhow::Result<Vec<IndexerKind>>>()?;
+
+    if uris.is_empty() {
+        anyhow::bail!("No indexer configured; set LIGHTWALLETD_URI or LIGHTWALLETD_URIS");
+    }
+    if kinds.len() != 1 && kinds.len() != uris.len() {
+        anyhow::bail!(
+            "INDEXER_KIND lists {} kinds for {} indexer URIs; give one kind, or one per URI",
+            kinds.len(),
+            uris.len()
+        );
+    }
+
+    Ok(uris
+        .into_iter()
+        .enumerate()
+        .map(|(i, uri)| Indexer { uri, kind: kinds[if kinds.len() == 1 { 0 } else { i }] })
+        .collect())
+}
+
+impl ActivationHeights {
+    fn from_env() -> anyhow::Result<Self> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the heights through `lookup`. Unset or empty variables take the
+    /// default; anything else that isn't a height is an error, since a
+    /// wallet on the wrong heights builds transactions Zebra rejects.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let height = |name: &str| -> anyhow::Result<Option<u32>> {
+            match lookup(name).filter(|s| !s.trim().is_empty()) {
+                Some(value) => value.trim().parse().map(Some).map_err(|e| {
+                    anyhow::anyhow!("Invalid {} {:?}: {}", name, value, e)
+                }),
+                None => Ok(None),
+            }
+        };
+        Ok(Self {
+            nu5: height("ACTIVATION_NU5")?.unwrap_or(1),
+            nu6: height("ACTIVATION_NU6")?,
+            nu6_1: height("ACTIVATION_NU6_1")?,
+            nu7: height("ACTIVATION_NU7")?,
+        })
+    }
 }
 
 impl Config {
@@ -17,8 +142,16 @@ impl Config {
             zingo_data_dir: std::env::var("ZINGO_DATA_DIR")
                 .unwrap_or_else(|_| "/var/zingo".to_string())
                 .into(),
-            lightwalletd_uri: std::env::var("LIGHTWALLETD_URI")
-                .unwrap_or_else(|_| "http://zaino:9067".to_string()),
+            indexers: indexers(
+                &std::env::var("LIGHTWALLETD_URIS")
+                    .or_else(|_| std::env::var("LIGHTWALLETD_URI"))
+                    .unwrap_or_else(|_| "http://zaino:9067".to_string()),
+                &std::env::var("INDEXER_KIND").unwrap_or_else(|_| "zaino".to_string()),
+            )?,
+            indexer_ready_timeout_secs: std::env::var("INDEXER_READY_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
             zebra_rpc_url: std::env::var("ZEBRA_RPC_URL")
                 .unwrap_or_else(|_| "http://zebra:8232".to_string()),
             faucet_amount_min: std::env::var("FAUCET_AMOUNT_MIN")
@@ -33,6 +166,93 @@ impl Config {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(10.0),
+            auto_shield_enabled: std::env::var("AUTO_SHIELD_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(true),
+            auto_shield_threshold: std::env::var("AUTO_SHIELD_THRESHOLD")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1.0),
+            auto_shield_interval_secs: std::env::var("AUTO_SHIELD_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(60),
+            refill_enabled: std::env::var("REFILL_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            refill_threshold: std::env::var("REFILL_THRESHOLD")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(10.0),
+            refill_blocks: std::env::var("REFILL_BLOCKS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(101),
+            refill_interval_secs: std::env::var("REFILL_INTERVAL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            keys_seed_enabled: std::env::var("KEYS_SEED_ENABLED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(false),
+            admin_api_key: std::env::var("ADMIN_API_KEY")
+                .ok()
+                .filter(|s| !s.trim().is_empty()),
+            ready_max_blocks_behind: std::env::var("READY_MAX_BLOCKS_BEHIND")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(10),
+            ready_min_balance: std::env::var("READY_MIN_BALANCE")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(1.0),
+            ready_max_sync_age_secs: std::env::var("READY_MAX_SYNC_AGE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
+            shutdown_timeout_secs: std::env::var("SHUTDOWN_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            activation_heights: ActivationHeights::from_env()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairs_indexer_uris_with_kinds() {
+        let both = indexers("http://zaino:9067, http://lightwalletd:9067", "zaino,lwd").unwrap();
+        assert_eq!(both[1], Indexer { uri: "http://lightwalletd:9067".into(), kind: IndexerKind::Lightwalletd });
+
+        let shared = indexers("http://a:9067,http://b:9067", "lightwalletd").unwrap();
+        assert!(shared.iter().all(|i| i.kind == IndexerKind::Lightwalletd));
+
+        assert!(indexers("http://a:9067,http://b:9067,http://c:9067", "zaino,zaino").is_err());
+        assert!(indexers("http://a:9067", "zcashd").is_err());
+        assert!(indexers("", "zaino").is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_activation_heights() {
+        let heights = ActivationHeights::from_lookup(|name| match name {
+            "ACTIVATION_NU6" => Some("5".to_string()),
+            "ACTIVATION_NU7" => Some(String::new()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((heights.nu5, heights.nu6, heights.nu7), (1, Some(5), None));
+
+        let error = ActivationHeights::from_lookup(|name| {
+            (name == "ACTIVATION_NU6_1").then(|| "six".to_string())
         })
+        .unwrap_err();
+        assert!(error.to_string().contains("ACTIVATION_NU6_1"));
     }
-}
\ No newline at end of file
+}
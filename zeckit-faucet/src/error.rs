@@ -26,6 +26,12 @@ pub enum FaucetError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Node RPC error: {0}")]
+    Rpc(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -39,6 +45,8 @@ impl IntoResponse for FaucetError {
             FaucetError::Validation(msg) => (StatusCode::BAD_REQUEST, msg),
             FaucetError::Wallet(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
             FaucetError::TransactionFailed(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            FaucetError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            FaucetError::Rpc(msg) => (StatusCode::BAD_GATEWAY, msg),
             FaucetError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
@@ -9,7 +9,6 @@ use tower_http::cors::CorsLayer;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use tokio::time::{sleep, Duration};
-use tonic::transport::Channel;
 use zcash_protocol::value::Zatoshis;
 
 mod config;
@@ -17,71 +16,32 @@ mod wallet;
 mod api;
 mod validation;
 mod error;
+mod zebra;
+mod shutdown;
+
+#[cfg(test)]
+mod tests;
 
 use config::Config;
-use wallet::WalletManager;
+use shutdown::{Shutdown, ShutdownSignal};
+use wallet::{SharedWallet, WalletManager};
+use wallet::auto_shield::AutoShieldPolicy;
+use wallet::backend::Backends;
+use wallet::refill::{RefillPolicy, RefillStatus};
+use wallet::sync::{tracked_sync, SyncStatus};
+use zebra::ZebraClient;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub wallet: Arc<RwLock<WalletManager>>,
+    pub wallet: SharedWallet,
     pub config: Arc<Config>,
+    pub refill: Arc<RwLock<RefillStatus>>,
+    pub sync: Arc<RwLock<SyncStatus>>,
+    pub backends: Arc<RwLock<Backends>>,
+    pub zebra: ZebraClient,
     pub start_time: chrono::DateTime<chrono::Utc>,
 }
 
-/// Health check for Zaino - uses lightweight gRPC ping instead of full sync
-async fn wait_for_zaino(uri: &str, max_attempts: u32) -> anyhow::Result<u64> {
-    use zcash_client_backend::proto::service::compact_tx_streamer_client::CompactTxStreamerClient;
-    use zcash_client_backend::proto::service::ChainSpec;
-    
-    info!(" Waiting for Zaino at {} to be ready...", uri);
-    
-    for attempt in 1..=max_attempts {
-        let ping_result = tokio::time::timeout(
-            Duration::from_secs(5),
-            async {
-                let channel = Channel::from_shared(uri.to_string())?
-                    .connect_timeout(Duration::from_secs(3))
-                    .connect()
-                    .await?;
-                
-                let mut client = CompactTxStreamerClient::new(channel);
-                let response = client.get_latest_block(ChainSpec {}).await?;
-                let block = response.into_inner();
-                
-                Ok::<u64, anyhow::Error>(block.height)
-            }
-        ).await;
-        
-        match ping_result {
-            Ok(Ok(height)) => {
-                info!(" Zaino ready at block height {} (took {}s)", height, attempt * 5);
-                return Ok(height);
-            }
-            Ok(Err(e)) => {
-                if attempt % 6 == 0 {  // Log every 30 seconds
-                    info!(" Still waiting for Zaino... ({}s elapsed)", attempt * 5);
-                    tracing::debug!("Zaino error: {}", e);
-                } else {
-                    tracing::debug!("Zaino not ready (attempt {}): {}", attempt, e);
-                }
-            }
-            Err(_) => {
-                if attempt % 6 == 0 {
-                    info!(" Still waiting for Zaino... ({}s elapsed) - connection timeout", attempt * 5);
-                } else {
-                    tracing::debug!("Zaino connection timeout (attempt {})", attempt);
-                }
-            }
-        }
-        
-        if attempt < max_attempts {
-            sleep(Duration::from_secs(5)).await;
-        }
-    }
-    
-    Err(anyhow::anyhow!("Zaino not ready after {} seconds", max_attempts * 5))
-}
-
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // ═══════════════════════════════════════════════════════════
@@ -103,15 +63,24 @@ async fn main() -> anyhow::Result<()> {
     let config = Config::load()?;
     info!("📋 Configuration loaded");
     info!("  Network: regtest");
-    info!("  Backend: {}", if config.lightwalletd_uri.contains("lightwalletd") { "lightwalletd" } else { "zaino" }); 
-    info!("  LightwalletD URI: {}", config.lightwalletd_uri);
+    for indexer in &config.indexers {
+        info!("  Indexer: {} at {}", indexer.kind.name(), indexer.uri);
+    }
     info!("  Data dir: {}", config.zingo_data_dir.display());
+    info!("  Activation heights: {:?}", config.activation_heights);
 
     // ═══════════════════════════════════════════════════════════
-    // STEP 3: Wait for Zaino Backend
+    // STEP 3: Wait for an Indexer Backend
     // ═══════════════════════════════════════════════════════════
-    let chain_height = wait_for_zaino(&config.lightwalletd_uri, 60).await?;
-    info!("🔗 Connected to Zaino at block {}", chain_height);
+    let (indexer, indexer_info) = wallet::backend::wait_ready(
+        &config.indexers,
+        Duration::from_secs(config.indexer_ready_timeout_secs),
+    ).await?;
+    info!(
+        "🔗 Connected to {} {} at block {}",
+        indexer.kind.name(), indexer_info.version, indexer_info.height
+    );
+    let backends = Arc::new(RwLock::new(Backends::new(config.indexers.clone(), indexer.clone())));
 
     // ═══════════════════════════════════════════════════════════
     // STEP 4: Initialize Wallet
@@ -119,10 +88,11 @@ async fn main() -> anyhow::Result<()> {
     info!("💼 Initializing wallet...");
     let wallet = WalletManager::new(
         config.zingo_data_dir.clone(),
-        config.lightwalletd_uri.clone(),
+        indexer.uri,
+        config.activation_heights,
     ).await?;
 
-    let wallet = Arc::new(RwLock::new(wallet));
+    let wallet: SharedWallet = Arc::new(RwLock::new(wallet));
 
     // Get wallet address
     let address = wallet.read().await.get_unified_address().await?;
@@ -134,23 +104,18 @@ async fn main() -> anyhow::Result<()> {
     // ═══════════════════════════════════════════════════════════
     info!("🔄 Performing initial wallet sync...");
     
+    let sync_status = Arc::new(RwLock::new(SyncStatus::default()));
+
     {
         let mut wallet_guard = wallet.write().await;
         
-        match tokio::time::timeout(
-            Duration::from_secs(120),
-            wallet_guard.sync()  // ← CHANGED from sync() to sync_and_await()
-        ).await {
-            Ok(Ok(result)) => {
+        match tracked_sync(&mut *wallet_guard, &sync_status, Duration::from_secs(120)).await {
+            Ok(()) => {
                 info!(" Initial sync completed successfully");
-                tracing::debug!("Sync result: {:?}", result);
             }
-            Ok(Err(e)) => {
+            Err(e) => {
                 tracing::warn!("⚠ Initial sync failed: {} (continuing anyway)", e);
             }
-            Err(_) => {
-                tracing::warn!("⏱ Initial sync timed out (continuing anyway)");
-            }
         }
     } // Release write lock
 
@@ -176,9 +141,15 @@ async fn main() -> anyhow::Result<()> {
     // ═══════════════════════════════════════════════════════════
     // STEP 6: Build Application State
     // ═══════════════════════════════════════════════════════════
+    let refill_status = Arc::new(RwLock::new(RefillStatus::new(&config)));
+
     let state = AppState {
         wallet: wallet.clone(),
         config: Arc::new(config.clone()),
+        refill: refill_status.clone(),
+        sync: sync_status.clone(),
+        backends: backends.clone(),
+        zebra: ZebraClient::new(&config.zebra_rpc_url),
         start_time: chrono::Utc::now(),
     };
 
@@ -186,9 +157,15 @@ async fn main() -> anyhow::Result<()> {
     // STEP 7: Start Background Sync Task 
     // ═══════════════════════════════════════════════════════════
     let sync_wallet = wallet.clone();
-    tokio::spawn(async move {
+    let background_sync_status = sync_status.clone();
+    let sync_backends = backends.clone();
+    let (shutdown, shutdown_signal) = Shutdown::new();
+    let mut sync_shutdown = shutdown_signal.clone();
+    let sync_task = tokio::spawn(async move {
         // Wait before starting to avoid collision with initial sync
-        sleep(Duration::from_secs(10)).await;
+        if !sleep_unless_cancelled(Duration::from_secs(10), &mut sync_shutdown).await {
+            return;
+        }
         
         info!("🔄 Starting background wallet sync (every 60 seconds)");
         
@@ -198,7 +175,10 @@ async fn main() -> anyhow::Result<()> {
         let mut sync_count = 0u64;
         
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = sync_shutdown.cancelled() => break,
+            }
             sync_count += 1;
             
             tracing::debug!("🔄 Background sync attempt #{}", sync_count);
@@ -211,17 +191,22 @@ async fn main() -> anyhow::Result<()> {
             
             match lock_result {
                 Ok(mut wallet_guard) => {
-                    // Perform sync_and_await with generous timeout
-                    let sync_result = tokio::time::timeout(
-                        Duration::from_secs(90), 
-                        wallet_guard.sync()
-                    ).await;
+                    // Perform sync_and_await with generous timeout; a shutdown
+                    // abandons it and shutdown's persist() stops zingolib's sync
+                    let sync_result = tokio::select! {
+                        result = tracked_sync(
+                            &mut *wallet_guard,
+                            &background_sync_status,
+                            Duration::from_secs(90),
+                        ) => result,
+                        _ = sync_shutdown.cancelled() => {
+                            background_sync_status.write().await.running = false;
+                            break;
+                        }
+                    };
                     
                     match sync_result {
-                        Ok(Ok(result)) => {
-                            // Sync completed successfully
-                            tracing::debug!("Sync result: {:?}", result);
-                            
+                        Ok(()) => {
                             // Release write lock before reading balance
                             drop(wallet_guard);
                             
@@ -234,11 +219,26 @@ async fn main() -> anyhow::Result<()> {
                                 }
                             }
                         }
-                        Ok(Err(e)) => {
+                        Err(e) => {
                             tracing::warn!("⚠ Sync #{} failed: {} (will retry in 60s)", sync_count, e);
-                        }
-                        Err(_) => {
-                            tracing::error!("⏱ Sync #{} timed out after 90s (will retry in 60s)", sync_count);
+
+                            // Fall back to another indexer if this one is down
+                            let switched = wallet::backend::failover(
+                                &mut *wallet_guard,
+                                &background_sync_status,
+                                &sync_backends,
+                                &e.to_string(),
+                            ).await;
+                            if let Some(indexer) = switched {
+                                info!("🔄 Rescanning through {} at {}", indexer.kind.name(), indexer.uri);
+                                if let Err(e) = tracked_sync(
+                                    &mut *wallet_guard,
+                                    &background_sync_status,
+                                    Duration::from_secs(90),
+                                ).await {
+                                    tracing::warn!("⚠ Sync after failover failed: {}", e);
+                                }
+                            }
                         }
                     }
                 }
@@ -247,30 +247,135 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
         }
+
+        info!("🔄 Background sync stopped");
     });
 
+    // ═══════════════════════════════════════════════════════════
+    // STEP 7b: Start Auto-Shield Task
+    // ═══════════════════════════════════════════════════════════
+    let auto_shield_policy = AutoShieldPolicy::from_config(&config);
+    match auto_shield_policy.clone() {
+        Some(policy) => {
+            let shield_wallet = wallet.clone();
+            let mut shield_shutdown = shutdown_signal.clone();
+            tokio::spawn(async move {
+                // Start after the background sync so the first balance read is fresh
+                if sleep_unless_cancelled(Duration::from_secs(20), &mut shield_shutdown).await {
+                    wallet::auto_shield::run(shield_wallet, policy, shield_shutdown).await;
+                }
+            });
+        }
+        None => info!("🛡 Auto-shield disabled"),
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // STEP 7c: Start Low-Balance Refill Watchdog
+    // ═══════════════════════════════════════════════════════════
+    let refill_policy = RefillPolicy::from_config(&config);
+    if let Some(policy) = refill_policy.clone() {
+        let refill_wallet = wallet.clone();
+        let refill_sync_status = sync_status.clone();
+        let zebra = state.zebra.clone();
+        let mut refill_shutdown = shutdown_signal.clone();
+        tokio::spawn(async move {
+            if sleep_unless_cancelled(Duration::from_secs(30), &mut refill_shutdown).await {
+                wallet::refill::run(
+                    refill_wallet,
+                    zebra,
+                    refill_status,
+                    refill_sync_status,
+                    policy,
+                    refill_shutdown,
+                ).await;
+            }
+        });
+    }
+
     // ═══════════════════════════════════════════════════════════
     // STEP 8: Build and Start Web Server
     // ═══════════════════════════════════════════════════════════
-    let app = Router::new()
+    let app = app(state.clone());
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+    info!("🌐 Server ready on {}", addr);
+    info!("📡 Background sync: Active (60s interval)");
+    if let Some(policy) = &auto_shield_policy {
+        info!("🛡 Auto-shield: Active ({}s interval)", policy.interval.as_secs());
+    }
+    if let Some(policy) = &refill_policy {
+        info!("⛏ Refill watchdog: Active ({}s interval)", policy.interval.as_secs());
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // STEP 9: Serve Until SIGTERM, Then Drain and Persist
+    // ═══════════════════════════════════════════════════════════
+    let shutdown_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown::wait_for_signal().await;
+            info!("🛑 Shutting down: no new requests, draining in-flight ones ({}s limit)", shutdown_timeout.as_secs());
+            shutdown.trigger();
+
+            // Nothing below may keep the process alive past the timeout
+            tokio::spawn(async move {
+                sleep(shutdown_timeout).await;
+                tracing::error!("❌ Shutdown took longer than {}s, exiting", shutdown_timeout.as_secs());
+                std::process::exit(1);
+            });
+        })
+        .await?;
+
+    // In-flight sends have finished; the sync loop drops its lock once it
+    // sees the shutdown signal
+    let _ = sync_task.await;
+
+    info!("💾 Saving wallet and history...");
+    let mut wallet = state.wallet.write().await;
+    match wallet.persist().await {
+        Ok(()) => info!("✓ Wallet state saved"),
+        Err(e) => tracing::error!("❌ Failed to save wallet state: {}", e),
+    }
+
+    info!("👋 Faucet stopped");
+    Ok(())
+}
+
+/// Sleep before a background task starts, returning `false` if the faucet
+/// starts shutting down in the meantime.
+async fn sleep_unless_cancelled(delay: Duration, shutdown: &mut ShutdownSignal) -> bool {
+    tokio::select! {
+        _ = sleep(delay) => true,
+        _ = shutdown.cancelled() => false,
+    }
+}
+
+/// All faucet routes on top of `state`.
+fn app(state: AppState) -> Router {
+    Router::new()
         .route("/", get(api::root))
         .route("/health", get(api::health::health_check))
+        .route("/livez", get(api::health::livez))
+        .route("/readyz", get(api::health::readyz))
         .route("/stats", get(api::stats::get_stats))
         .route("/history", get(api::stats::get_history))
         .route("/request", post(api::faucet::request_funds))
         .route("/address", get(api::wallet::get_addresses))
+        .route("/address/new", post(api::wallet::new_address))
+        .route("/addresses", get(api::wallet::list_addresses))
         .route("/sync", post(api::wallet::sync_wallet))
+        .route("/sync/status", get(api::wallet::sync_status))
         .route("/shield", post(api::wallet::shield_funds)) 
         .route("/send", post(api::wallet::send_shielded))
+        .route("/accounts", get(api::accounts::list_accounts).post(api::accounts::create_account))
+        .route("/accounts/:name/balance", get(api::accounts::get_account_balance))
+        .route("/wallets/ephemeral", post(api::wallets::create_ephemeral))
+        .route("/keys/viewing", get(api::keys::get_viewing_keys))
+        .route("/keys/seed", get(api::keys::get_seed))
+        .route("/admin/rescan", post(api::admin::rescan))
+        .route("/admin/reset-wallet", post(api::admin::reset_wallet))
+        .route("/admin/backend", get(api::admin::get_backend).post(api::admin::set_backend))
         .layer(CorsLayer::permissive())
-        .with_state(state);
-
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
-    info!("🌐 Server ready on {}", addr);
-    info!("📡 Background sync: Active (60s interval)");
-
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
-
-    Ok(())
+        .with_state(state)
 }
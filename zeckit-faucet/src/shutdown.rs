@@ -0,0 +1,61 @@
+use tokio::sync::watch;
+use tracing::info;
+
+/// Tells background tasks that the faucet is shutting down.
+///
+/// Loops select on [`ShutdownSignal::cancelled`] next to their interval tick
+/// so they stop between iterations instead of being killed mid-operation.
+#[derive(Clone)]
+pub struct ShutdownSignal(watch::Receiver<bool>);
+
+impl ShutdownSignal {
+    /// Resolves once shutdown has started.
+    pub async fn cancelled(&mut self) {
+        // An error means the sender is gone, which only happens on exit
+        let _ = self.0.wait_for(|stopping| *stopping).await;
+    }
+}
+
+pub struct Shutdown(watch::Sender<bool>);
+
+impl Shutdown {
+    pub fn new() -> (Self, ShutdownSignal) {
+        let (tx, rx) = watch::channel(false);
+        (Self(tx), ShutdownSignal(rx))
+    }
+
+    pub fn trigger(&self) {
+        self.0.send_replace(true);
+    }
+}
+
+/// Resolves on SIGTERM (`docker compose down`/`docker stop`) or Ctrl-C.
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("⚠ Could not listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("⚠ Could not listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("🛑 Received Ctrl-C"),
+        _ = terminate => info!("🛑 Received SIGTERM"),
+    }
+}
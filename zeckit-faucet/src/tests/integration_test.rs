@@ -1,59 +1,600 @@
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::tempdir;
-
-    #[tokio::test]
-    async fn test_wallet_initialization() {
-        let temp_dir = tempdir().unwrap();
-        let server_uri = "http://localhost:9067".to_string();
-
-        // This would need a running regtest network
-        // For now, we test the config loading
-        let config = Config {
-            zingo_data_dir: temp_dir.path().to_path_buf(),
-            lightwalletd_uri: server_uri,
-            zebra_rpc_url: "http://localhost:8232".to_string(),
-            faucet_amount_min: 0.01,
-            faucet_amount_max: 100.0,
-            faucet_amount_default: 10.0,
-        };
-
-        assert_eq!(config.faucet_amount_min, 0.01);
-        assert_eq!(config.faucet_amount_max, 100.0);
-    }
-
-    #[test]
-    fn test_balance_calculations() {
-        let balance = Balance {
-            transparent: 100_000_000, // 1 ZEC
-            sapling: 200_000_000,     // 2 ZEC
-            orchard: 300_000_000,     // 3 ZEC
-        };
-
-        assert_eq!(balance.total_zatoshis(), 600_000_000);
-        assert_eq!(balance.total_zec(), 6.0);
-        assert_eq!(balance.orchard_zec(), 3.0);
-        assert_eq!(balance.transparent_zec(), 1.0);
-    }
-
-    #[test]
-    fn test_transaction_history() {
-        let temp_dir = tempdir().unwrap();
-        let mut history = TransactionHistory::load(temp_dir.path()).unwrap();
-
-        let record = TransactionRecord {
-            timestamp: chrono::Utc::now(),
-            to_address: "uregtest1test123".to_string(),
-            amount: 10.0,
-            txid: "abc123".to_string(),
-            memo: "test".to_string(),
-        };
-
-        history.add_transaction(record.clone()).unwrap();
-
-        let recent = history.get_recent(1);
-        assert_eq!(recent.len(), 1);
-        assert_eq!(recent[0].amount, 10.0);
-    }
-}
\ No newline at end of file
+//! Route tests against the in-memory `MockWallet`: no chain, indexer or
+//! Zebra needed. Zebra RPCs are answered by a mockito server where a route
+//! calls them; the indexer URI points at a closed port.
+
+use crate::config::{ActivationHeights, Config, Indexer, IndexerKind};
+use crate::wallet::backend::Backends;
+use crate::wallet::history::TransactionHistory;
+use crate::wallet::manager::Balance;
+use crate::wallet::mock::MockWallet;
+use crate::wallet::refill::RefillStatus;
+use crate::wallet::sync::SyncStatus;
+use crate::wallet::{FaucetWallet, SharedWallet};
+use crate::zebra::ZebraClient;
+use crate::{app, AppState};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use mockito::{Matcher, Mock, ServerGuard};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+use zcash_protocol::value::Zatoshis;
+
+/// Valid regtest transparent address to send to.
+const RECIPIENT: &str = "tmGWyihj4Q64yHJutdHKC5FEg2CjzSf2CJ4";
+
+/// Nothing listens here, so indexer probes fail fast.
+const DEAD_INDEXER: &str = "http://127.0.0.1:1";
+
+/// Admin key the test faucet is configured with.
+const ADMIN_KEY: &str = "admin-secret";
+
+/// Zebra for tests whose routes never call it; any call fails fast.
+const DEAD_ZEBRA: &str = "http://127.0.0.1:1";
+
+struct Faucet {
+    app: Router,
+    wallet: Arc<RwLock<MockWallet>>,
+    state: AppState,
+}
+
+fn mock_faucet(wallet: MockWallet) -> Faucet {
+    mock_faucet_with(wallet, DEAD_ZEBRA, |_| {})
+}
+
+/// Build the faucet around `wallet`. The config is spelled out rather than
+/// read from the environment, so tests don't depend on the shell they run in.
+fn mock_faucet_with(wallet: MockWallet, zebra_url: &str, configure: impl FnOnce(&mut Config)) -> Faucet {
+    let indexer = Indexer { uri: DEAD_INDEXER.to_string(), kind: IndexerKind::Zaino };
+    let mut config = Config {
+        zingo_data_dir: "/nonexistent/zingo".into(),
+        indexers: vec![indexer.clone()],
+        indexer_ready_timeout_secs: 1,
+        zebra_rpc_url: zebra_url.to_string(),
+        faucet_amount_min: 0.01,
+        faucet_amount_max: 100.0,
+        faucet_amount_default: 10.0,
+        auto_shield_enabled: false,
+        auto_shield_threshold: 1.0,
+        auto_shield_interval_secs: 60,
+        refill_enabled: false,
+        refill_threshold: 10.0,
+        refill_blocks: 101,
+        refill_interval_secs: 30,
+        keys_seed_enabled: false,
+        admin_api_key: Some(ADMIN_KEY.to_string()),
+        ready_max_blocks_behind: 10,
+        ready_min_balance: 1.0,
+        ready_max_sync_age_secs: 300,
+        shutdown_timeout_secs: 30,
+        activation_heights: ActivationHeights { nu5: 1, nu6: None, nu6_1: None, nu7: None },
+    };
+    configure(&mut config);
+
+    let wallet = Arc::new(RwLock::new(wallet));
+    let shared: SharedWallet = wallet.clone();
+
+    let state = AppState {
+        wallet: shared,
+        refill: Arc::new(RwLock::new(RefillStatus::new(&config))),
+        sync: Arc::new(RwLock::new(SyncStatus::default())),
+        backends: Arc::new(RwLock::new(Backends::new(vec![indexer.clone()], indexer))),
+        zebra: ZebraClient::new(&config.zebra_rpc_url),
+        config: Arc::new(config),
+        start_time: chrono::Utc::now(),
+    };
+
+    Faucet { app: app(state.clone()), wallet, state }
+}
+
+impl Faucet {
+    async fn get(&self, uri: &str) -> (StatusCode, Value) {
+        self.get_as(uri, None).await
+    }
+
+    async fn get_as(&self, uri: &str, api_key: Option<&str>) -> (StatusCode, Value) {
+        let mut request = Request::get(uri);
+        if let Some(key) = api_key {
+            request = request.header("x-api-key", key);
+        }
+        self.send(request.body(Body::empty()).unwrap()).await
+    }
+
+    async fn post(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        self.post_as(uri, body, None).await
+    }
+
+    async fn post_as(&self, uri: &str, body: Value, api_key: Option<&str>) -> (StatusCode, Value) {
+        let mut request = Request::post(uri).header("content-type", "application/json");
+        if let Some(key) = api_key {
+            request = request.header("x-api-key", key);
+        }
+        self.send(request.body(Body::from(body.to_string())).unwrap()).await
+    }
+
+    async fn post_admin(&self, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::post(uri)
+            .header("content-type", "application/json")
+            .header("x-admin-key", ADMIN_KEY);
+        self.send(request.body(Body::from(body.to_string())).unwrap()).await
+    }
+
+    async fn send(&self, request: Request<Body>) -> (StatusCode, Value) {
+        let response = self.app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+}
+
+/// A Zebra stand-in answering `method` with `result`.
+async fn zebra_rpc(server: &mut ServerGuard, method: &str, result: Value) -> Mock {
+    server
+        .mock("POST", "/")
+        .match_body(Matcher::PartialJson(json!({ "method": method })))
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "jsonrpc": "2.0", "id": method, "result": result }).to_string())
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_root_livez_and_health() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 0.0, 50.0));
+
+    let (status, root) = faucet.get("/").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(root["endpoints"]["request"], "/request");
+
+    let (status, live) = faucet.get("/livez").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(live["status"], "alive");
+
+    let (status, health) = faucet.get("/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(health["orchard_balance"], 50.0);
+    assert_eq!(health["indexer"]["uri"], DEAD_INDEXER);
+    assert!(health["indexer"]["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_readyz_reports_failing_components() {
+    let mut zebra = mockito::Server::new_async().await;
+    let _count = zebra_rpc(&mut zebra, "getblockcount", json!(100)).await;
+    let faucet = mock_faucet_with(MockWallet::new().fund(0, 0.0, 50.0), &zebra.url(), |_| {});
+
+    {
+        let mut sync = faucet.state.sync.write().await;
+        sync.wallet_height = Some(98);
+        sync.orchard_zatoshis = Some(5_000_000_000);
+        sync.last_sync = Some(chrono::Utc::now());
+    }
+
+    let (status, ready) = faucet.get("/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(ready["components"]["indexer"]["status"], "fail");
+    assert_eq!(ready["components"]["sync"]["status"], "ok");
+    assert_eq!(ready["components"]["balance"]["status"], "ok");
+    assert_eq!(ready["components"]["last_sync"]["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_request_sends_and_updates_stats_and_history() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 0.0, 50.0));
+
+    let (status, sent) = faucet.post("/request", json!({ "address": RECIPIENT, "amount": 2.5, "memo": "hi" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sent["success"], true);
+    assert_eq!(sent["new_balance"], 47.5);
+
+    let (_, stats) = faucet.get("/stats").await;
+    assert_eq!(stats["total_requests"], 1);
+    assert_eq!(stats["total_sent"], 2.5);
+    assert_eq!(stats["current_balance"], 47.5);
+
+    let (_, history) = faucet.get("/history?limit=10").await;
+    assert_eq!(history["count"], 1);
+    assert_eq!(history["transactions"][0]["to_address"], RECIPIENT);
+    assert_eq!(history["transactions"][0]["memo"], "hi");
+    assert_eq!(history["transactions"][0]["txid"], sent["txid"]);
+}
+
+#[tokio::test]
+async fn test_request_rejects_bad_input() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 0.0, 1.0));
+
+    let (status, _) = faucet.post("/request", json!({ "address": "not_an_address" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = faucet.post("/request", json!({ "address": RECIPIENT, "amount": 1000.0 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, error) = faucet.post("/request", json!({ "address": RECIPIENT, "amount": 5.0 })).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(error["error"].as_str().unwrap().contains("Orchard"));
+
+    assert!(faucet.wallet.read().await.history().get_all().is_empty());
+}
+
+#[tokio::test]
+async fn test_addresses_are_derived_and_listed() {
+    let faucet = mock_faucet(MockWallet::new());
+
+    let (status, addresses) = faucet.get("/address").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(addresses["unified_address"], "uregtest1mockaccount0");
+
+    let (status, derived) = faucet.post("/address/new?receivers=orchard,transparent", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(derived["addresses"].as_array().unwrap().len(), 2);
+    assert_eq!(derived["receivers"], json!(["orchard", "transparent"]));
+
+    let unified = derived["addresses"][0]["address"].as_str().unwrap().to_string();
+    faucet.wallet.write().await.received.insert(unified.clone(), 150_000_000);
+
+    let (_, listed) = faucet.get("/addresses").await;
+    assert_eq!(listed["count"], 2);
+    assert_eq!(listed["addresses"][0]["address"], unified);
+    assert_eq!(listed["addresses"][0]["received"], 1.5);
+
+    let (status, sapling) = faucet.post("/address/new?receivers=sapling", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sapling["addresses"][0]["kind"], "unified");
+    assert_eq!(sapling["addresses"][1]["kind"], "sapling");
+    assert!(sapling["addresses"][1]["address"].as_str().unwrap().starts_with("zregtestsapling"));
+
+    let (status, _) = faucet.post("/address/new?receivers=bogus", json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_sync_and_sync_status() {
+    let mut zebra = mockito::Server::new_async().await;
+    let _count = zebra_rpc(&mut zebra, "getblockcount", json!(120)).await;
+    let mut wallet = MockWallet::new();
+    wallet.chain_height = 120;
+    let faucet = mock_faucet_with(wallet, &zebra.url(), |_| {});
+
+    let (status, synced) = faucet.post("/sync", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(synced["status"], "synced");
+    assert_eq!(faucet.wallet.read().await.sync_count, 1);
+
+    let (status, progress) = faucet.get("/sync/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["wallet_height"], 120);
+    assert_eq!(progress["chain_height"], 120);
+    assert_eq!(progress["blocks_behind"], 0);
+    assert_eq!(progress["sync_count"], 1);
+
+    faucet.wallet.write().await.fail_sync = Some("indexer gone".to_string());
+    let (status, _) = faucet.post("/sync", json!({})).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    let (_, progress) = faucet.get("/sync/status").await;
+    assert!(progress["last_error"].as_str().unwrap().contains("indexer gone"));
+}
+
+#[tokio::test]
+async fn test_background_sync_is_claimed_once() {
+    let faucet = mock_faucet(MockWallet::new());
+
+    // Hold the wallet so the spawned sync can't start yet
+    let wallet = faucet.wallet.write().await;
+    let (status, first) = faucet.post("/sync?wait=false", json!({})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(first["status"], "started");
+
+    let (_, second) = faucet.post("/sync?wait=false", json!({})).await;
+    assert_eq!(second["status"], "already_running");
+
+    let (_, progress) = faucet.get("/sync/status").await;
+    assert_eq!(progress["running"], true);
+    drop(wallet);
+
+    while faucet.state.sync.read().await.running {
+        tokio::task::yield_now().await;
+    }
+    assert_eq!(faucet.wallet.read().await.sync_count, 1);
+}
+
+#[tokio::test]
+async fn test_shield_moves_transparent_funds() {
+    let faucet = mock_faucet(MockWallet::new());
+
+    let (status, nothing) = faucet.post("/shield", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(nothing["status"], "no_funds");
+
+    faucet.wallet.write().await.set_balance(0, 2.0, 0.0);
+    let (status, shielded) = faucet.post("/shield", json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(shielded["status"], "shielded");
+    assert_eq!(shielded["transparent_amount"], 2.0);
+
+    let balance: Balance = faucet.wallet.read().await.balance(0);
+    assert_eq!(balance.transparent, Zatoshis::ZERO);
+    assert_eq!(balance.orchard.into_u64(), 200_000_000 - 10_000);
+
+    // Shields are internal and don't count as faucet requests
+    let (_, stats) = faucet.get("/stats").await;
+    assert_eq!(stats["total_requests"], 0);
+}
+
+#[tokio::test]
+async fn test_send_checks_orchard_balance() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 5.0, 1.0));
+
+    let (status, _) = faucet.post("/send", json!({ "address": RECIPIENT, "amount": 2.0 })).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    let (status, sent) = faucet.post("/send", json!({
+        "address": RECIPIENT,
+        "amount": 0.5,
+        "memo": { "text": "thanks" }
+    })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sent["status"], "sent");
+    assert_eq!(sent["orchard_balance"], 0.5);
+    assert_eq!(sent["memo"], "thanks");
+}
+
+#[tokio::test]
+async fn test_accounts_are_isolated_by_api_key() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 0.0, 10.0));
+
+    let (status, created) = faucet.post_admin("/accounts", json!({ "name": "team-a", "api_key": "secret-a" })).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["index"], 1);
+    assert_eq!(created["unified_address"], "uregtest1mockaccount1");
+    faucet.wallet.write().await.set_balance(1, 0.0, 3.0);
+
+    let (status, _) = faucet.post_admin("/accounts", json!({ "name": "team-a" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, listed) = faucet.get("/accounts").await;
+    assert_eq!(listed["count"], 2);
+    assert_eq!(listed["accounts"][1]["name"], "team-a");
+
+    let (status, balance) = faucet.get_as("/accounts/team-a/balance", Some("secret-a")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(balance["orchard_balance"], 3.0);
+
+    // Adding the account left the default account's funds in place
+    let (_, balance) = faucet.get("/accounts/default/balance").await;
+    assert_eq!(balance["orchard_balance"], 10.0);
+
+    // The key pins the request to team-a's funds, whatever the body says
+    let (status, sent) = faucet.post_as("/request", json!({ "address": RECIPIENT, "amount": 1.0 }), Some("secret-a")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sent["new_balance"], 2.0);
+
+    let (status, _) = faucet.post_as(
+        "/request",
+        json!({ "address": RECIPIENT, "amount": 1.0, "account": "default" }),
+        Some("secret-a"),
+    ).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = faucet.post_as("/request", json!({ "address": RECIPIENT }), Some("wrong")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (_, history) = faucet.get_as("/history?account=team-a", Some("secret-a")).await;
+    assert_eq!(history["count"], 1);
+    assert_eq!(history["account"], "team-a");
+}
+
+#[tokio::test]
+async fn test_account_creation_requires_admin_key() {
+    let faucet = mock_faucet(MockWallet::new());
+
+    let (status, _) = faucet.post("/accounts", json!({ "name": "team-a" })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let request = Request::post("/accounts")
+        .header("content-type", "application/json")
+        .header("x-admin-key", "wrong")
+        .body(Body::from(json!({ "name": "team-a" }).to_string()))
+        .unwrap();
+    let (status, _) = faucet.send(request).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let disabled = mock_faucet_with(MockWallet::new(), DEAD_ZEBRA, |config| config.admin_api_key = None);
+    let (status, _) = disabled.post_admin("/accounts", json!({ "name": "team-a" })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (_, listed) = faucet.get("/accounts").await;
+    assert_eq!(listed["count"], 1);
+}
+
+#[tokio::test]
+async fn test_key_bound_account_cannot_be_selected_without_its_key() {
+    let mut zebra = mockito::Server::new_async().await;
+    let _count = zebra_rpc(&mut zebra, "getblockcount", json!(200)).await;
+    let faucet = mock_faucet_with(MockWallet::new().fund(0, 0.0, 10.0), &zebra.url(), |_| {});
+    faucet.post_admin("/accounts", json!({ "name": "team-a", "api_key": "secret-a" })).await;
+    faucet.post_admin("/accounts", json!({ "name": "team-b" })).await;
+    faucet.wallet.write().await.set_balance(1, 0.0, 3.0);
+    faucet.wallet.write().await.set_balance(2, 0.0, 3.0);
+
+    for account in ["team-a", "1"] {
+        let request = json!({ "address": RECIPIENT, "amount": 1.0, "account": account });
+        let (status, _) = faucet.post("/request", request.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = faucet.post("/send", request).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    let (status, _) = faucet.post("/wallets/ephemeral", json!({ "amount": 1.0, "account": "team-a" })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = faucet.get("/history?account=team-a").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = faucet.get("/accounts/team-a/balance").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    assert_eq!(faucet.wallet.read().await.balance(1).orchard_zec(), 3.0);
+
+    // Accounts created without a key stay open to everyone
+    let (status, sent) = faucet.post("/request", json!({ "address": RECIPIENT, "amount": 1.0, "account": "team-b" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(sent["new_balance"], 2.0);
+}
+
+#[tokio::test]
+async fn test_keys_and_seed_export() {
+    let faucet = mock_faucet(MockWallet::new());
+
+    let (status, keys) = faucet.get("/keys/viewing").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(keys["account"], "default");
+    assert_eq!(keys["ufvk"], "uviewregtest1mock0");
+    assert_eq!(keys["birthday"], 1);
+
+    let (status, _) = faucet.get("/keys/seed").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let faucet = mock_faucet_with(MockWallet::new(), DEAD_ZEBRA, |config| config.keys_seed_enabled = true);
+    let (status, seed) = faucet.get("/keys/seed").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(seed["seed"].as_str().unwrap().ends_with("art"));
+}
+
+#[tokio::test]
+async fn test_ephemeral_wallet_is_funded_and_confirmed() {
+    let mut zebra = mockito::Server::new_async().await;
+    let _count = zebra_rpc(&mut zebra, "getblockcount", json!(200)).await;
+    let _tx = zebra_rpc(&mut zebra, "getrawtransaction", json!({ "confirmations": 1 })).await;
+    let faucet = mock_faucet_with(MockWallet::new().fund(0, 0.0, 10.0), &zebra.url(), |_| {});
+
+    let (status, ephemeral) = faucet.post("/wallets/ephemeral", json!({
+        "amount": 1.0,
+        "pools": ["orchard", "transparent"]
+    })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ephemeral["birthday"], 200);
+    assert_eq!(ephemeral["blocks_mined"], 0);
+    assert_eq!(ephemeral["pools"], json!(["orchard", "transparent"]));
+    assert!(ephemeral["sapling_address"].as_str().unwrap().starts_with("zregtestsapling"));
+
+    let wallet = faucet.wallet.read().await;
+    assert_eq!(wallet.balance(0).orchard_zec(), 8.0);
+    assert_eq!(wallet.history().get_all().len(), 2);
+}
+
+#[tokio::test]
+async fn test_ephemeral_wallet_limits() {
+    let mut zebra = mockito::Server::new_async().await;
+    let _count = zebra_rpc(&mut zebra, "getblockcount", json!(200)).await;
+    let faucet = mock_faucet_with(MockWallet::new().fund(0, 0.0, 500.0), &zebra.url(), |config| {
+        config.faucet_amount_max = 100.0;
+    });
+
+    let (status, _) = faucet.post("/wallets/ephemeral", json!({ "confirmations": 1_000_000 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = faucet.post("/wallets/ephemeral", json!({ "confirmations": u32::MAX })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, error) = faucet.post("/wallets/ephemeral", json!({
+        "amount": 40.0,
+        "pools": ["orchard", "sapling", "transparent"]
+    })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("maximum"));
+
+    assert!(faucet.wallet.read().await.history().get_all().is_empty());
+}
+
+#[tokio::test]
+async fn test_admin_rescan_reset_and_backend() {
+    let faucet = mock_faucet(MockWallet::new().fund(0, 0.0, 10.0));
+    faucet.post("/request", json!({ "address": RECIPIENT, "amount": 1.0 })).await;
+
+    let (status, rescan) = faucet.post("/admin/rescan?from_height=5", json!({})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(rescan["from_height"], 5);
+    assert_eq!(rescan["rescan_id"], 1);
+    assert_eq!(faucet.wallet.read().await.history().get_all().len(), 1);
+
+    // The spawned rescan takes the wallet lock once the handler is done
+    while faucet.wallet.read().await.sync_count == 0 {
+        tokio::task::yield_now().await;
+    }
+    let (_, progress) = faucet.get("/sync/status").await;
+    assert_eq!(progress["rescan"]["id"], 1);
+    assert_eq!(progress["rescan"]["from_height"], 5);
+    assert!(progress["rescan"]["finished_at"].is_string());
+    assert!(progress["rescan"]["error"].is_null());
+
+    faucet.wallet.write().await.fail_sync = Some("indexer gone".to_string());
+    let (status, reset) = faucet.post("/admin/reset-wallet", json!({})).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(reset["rescan_id"], 2);
+    assert!(faucet.wallet.read().await.history().get_all().is_empty());
+
+    let progress = loop {
+        let (_, progress) = faucet.get("/sync/status").await;
+        if progress["rescan"]["finished_at"].is_string() {
+            break progress;
+        }
+        tokio::task::yield_now().await;
+    };
+    assert_eq!(progress["rescan"]["id"], 2);
+    assert!(progress["rescan"]["error"].as_str().unwrap().contains("indexer gone"));
+
+    let (status, backends) = faucet.get("/admin/backend").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(backends["active"]["uri"], DEAD_INDEXER);
+
+    let (status, _) = faucet.post("/admin/backend", json!({ "backend": "lightwalletd" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Configured but unreachable: the wallet is left alone
+    let (status, _) = faucet.post("/admin/backend", json!({ "backend": "zaino" })).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(faucet.state.backends.read().await.switch_count, 0);
+}
+
+#[test]
+fn test_balance_calculations() {
+    let balance = Balance {
+        transparent: Zatoshis::from_u64(100_000_000).unwrap(),
+        sapling: Zatoshis::from_u64(200_000_000).unwrap(),
+        orchard: Zatoshis::from_u64(300_000_000).unwrap(),
+    };
+
+    assert_eq!(balance.total_zatoshis().into_u64(), 600_000_000);
+    assert_eq!(balance.total_zec(), 6.0);
+    assert_eq!(balance.orchard_zec(), 3.0);
+    assert_eq!(balance.transparent_zec(), 1.0);
+    assert_eq!(balance.sapling_zec(), 2.0);
+}
+
+#[test]
+fn test_transaction_history_survives_reload() {
+    use crate::wallet::history::{TransactionKind, TransactionRecord};
+
+    let temp_dir = tempdir().unwrap();
+    let mut history = TransactionHistory::load(temp_dir.path()).unwrap();
+
+    history.add_transaction(TransactionRecord {
+        kind: TransactionKind::Send,
+        account: 0,
+        timestamp: chrono::Utc::now(),
+        to_address: "uregtest1test123".to_string(),
+        amount: 10.0,
+        txid: "abc123".to_string(),
+        memo: "test".to_string(),
+        memo_hex: None,
+    }).unwrap();
+
+    let reloaded = TransactionHistory::load(temp_dir.path()).unwrap();
+    let recent = reloaded.get_recent(1);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].amount, 10.0);
+}
@@ -0,0 +1,135 @@
+use crate::error::FaucetError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the faucet's own account (ZIP-32 account 0). It receives the
+/// mined coinbase and is always present, so it is not stored in the registry.
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountRecord {
+    pub name: String,
+    pub index: u32,
+    /// Requests carrying this key in `X-Api-Key` are pinned to this account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Named sub-accounts of the faucet wallet, one per tenant.
+///
+/// Each entry is a ZIP-32 account derived from the faucet seed, so every
+/// team sharing the devnet gets its own balance and history.
+pub struct AccountRegistry {
+    file_path: PathBuf,
+    accounts: Vec<AccountRecord>,
+}
+
+impl AccountRegistry {
+    pub fn load(data_dir: &Path) -> Result<Self, FaucetError> {
+        let file_path = data_dir.join("faucet-accounts.json");
+
+        let accounts = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)
+                .map_err(|e| FaucetError::Internal(format!("Failed to read accounts: {}", e)))?;
+
+            serde_json::from_str(&content)
+                .map_err(|e| FaucetError::Internal(format!("Failed to parse accounts: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            accounts,
+        })
+    }
+
+    /// Number of ZIP-32 accounts the wallet must hold, including the default one.
+    pub fn account_count(&self) -> u32 {
+        self.accounts.len() as u32 + 1
+    }
+
+    /// Check that an account called `name` with `api_key` could be added.
+    pub fn validate(&self, name: &str, api_key: Option<&str>) -> Result<(), FaucetError> {
+        if name.is_empty() || name == DEFAULT_ACCOUNT || name.parse::<u32>().is_ok() {
+            return Err(FaucetError::Validation(format!("Invalid account name: {:?}", name)));
+        }
+        if self.accounts.iter().any(|a| a.name == name) {
+            return Err(FaucetError::Validation(format!("Account {:?} already exists", name)));
+        }
+        if let Some(key) = api_key {
+            if self.accounts.iter().any(|a| a.api_key.as_deref() == Some(key)) {
+                return Err(FaucetError::Validation("API key is already assigned".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, name: &str, api_key: Option<String>) -> Result<AccountRecord, FaucetError> {
+        self.validate(name, api_key.as_deref())?;
+
+        let record = AccountRecord {
+            name: name.to_string(),
+            index: self.account_count(),
+            api_key,
+            created_at: Utc::now(),
+        };
+
+        self.accounts.push(record.clone());
+        self.save()?;
+        Ok(record)
+    }
+
+    pub fn get_all(&self) -> &[AccountRecord] {
+        &self.accounts
+    }
+
+    /// Resolve an account by name or index. `default` and `0` are the faucet's own account.
+    pub fn find(&self, selector: &str) -> Option<u32> {
+        if selector == DEFAULT_ACCOUNT {
+            return Some(0);
+        }
+        if let Ok(index) = selector.parse::<u32>() {
+            return (index < self.account_count()).then_some(index);
+        }
+        self.accounts.iter().find(|a| a.name == selector).map(|a| a.index)
+    }
+
+    pub fn find_by_api_key(&self, api_key: &str) -> Option<u32> {
+        self.accounts
+            .iter()
+            .find(|a| a.api_key.as_deref() == Some(api_key))
+            .map(|a| a.index)
+    }
+
+    /// Whether the account at `index` is bound to an API key and so only
+    /// usable by requests carrying that key.
+    pub fn requires_api_key(&self, index: u32) -> bool {
+        self.accounts.iter().any(|a| a.index == index && a.api_key.is_some())
+    }
+
+    pub fn name_of(&self, index: u32) -> String {
+        self.accounts
+            .iter()
+            .find(|a| a.index == index)
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| DEFAULT_ACCOUNT.to_string())
+    }
+
+    /// Write to a temporary file and rename it over the old one, so a crash
+    /// mid-write never loses the name, index and API key mapping.
+    fn save(&self) -> Result<(), FaucetError> {
+        let json = serde_json::to_string_pretty(&self.accounts)
+            .map_err(|e| FaucetError::Internal(format!("Failed to serialize accounts: {}", e)))?;
+
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, &self.file_path))
+            .map_err(|e| FaucetError::Internal(format!("Failed to write accounts: {}", e)))?;
+
+        Ok(())
+    }
+}
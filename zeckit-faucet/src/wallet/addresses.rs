@@ -0,0 +1,150 @@
+use crate::error::FaucetError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use zcash_address::unified::{self, Container, Encoding};
+use zcash_address::ZcashAddress;
+
+/// Receiver types requested for a new address, parsed from
+/// `?receivers=orchard,sapling,transparent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Receivers {
+    pub orchard: bool,
+    pub sapling: bool,
+    pub transparent: bool,
+}
+
+impl Default for Receivers {
+    fn default() -> Self {
+        Self { orchard: true, sapling: true, transparent: false }
+    }
+}
+
+impl Receivers {
+    pub fn parse(list: &str) -> Result<Self, FaucetError> {
+        let mut receivers = Self { orchard: false, sapling: false, transparent: false };
+
+        for receiver in list.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            match receiver.to_ascii_lowercase().as_str() {
+                "orchard" | "o" => receivers.orchard = true,
+                "sapling" | "s" | "z" => receivers.sapling = true,
+                "transparent" | "t" | "p2pkh" => receivers.transparent = true,
+                other => {
+                    return Err(FaucetError::Validation(format!(
+                        "Unknown receiver {:?} (expected orchard, sapling or transparent)",
+                        other
+                    )))
+                }
+            }
+        }
+
+        if !(receivers.orchard || receivers.sapling || receivers.transparent) {
+            return Err(FaucetError::Validation("At least one receiver is required".to_string()));
+        }
+
+        Ok(receivers)
+    }
+
+    pub fn has_shielded(&self) -> bool {
+        self.orchard || self.sapling
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        [
+            (self.orchard, "orchard"),
+            (self.sapling, "sapling"),
+            (self.transparent, "transparent"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, name)| name.to_string())
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressKind {
+    Unified,
+    /// Bare `zregtestsapling` address, for wallets that predate unified addresses.
+    Sapling,
+    Transparent,
+}
+
+/// The Sapling receiver of a unified address, encoded as a standalone
+/// Sapling address on the same network.
+pub fn sapling_receiver(unified_address: &str) -> Option<String> {
+    let (network, address) = unified::Address::decode(unified_address).ok()?;
+
+    address.items().into_iter().find_map(|receiver| match receiver {
+        unified::Receiver::Sapling(data) => Some(ZcashAddress::from_sapling(network, data).encode()),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedAddress {
+    pub address: String,
+    pub kind: AddressKind,
+    pub receivers: Vec<String>,
+    pub account: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Addresses derived through `POST /address/new`, kept so they can be
+/// listed with their received totals after restarts.
+pub struct AddressBook {
+    file_path: PathBuf,
+    addresses: Vec<DerivedAddress>,
+}
+
+impl AddressBook {
+    pub fn load(data_dir: &Path) -> Result<Self, FaucetError> {
+        let file_path = data_dir.join("faucet-addresses.json");
+
+        let addresses = if file_path.exists() {
+            let content = fs::read_to_string(&file_path)
+                .map_err(|e| FaucetError::Internal(format!("Failed to read address book: {}", e)))?;
+
+            serde_json::from_str(&content)
+                .map_err(|e| FaucetError::Internal(format!("Failed to parse address book: {}", e)))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            file_path,
+            addresses,
+        })
+    }
+
+    pub fn add(&mut self, address: DerivedAddress) -> Result<(), FaucetError> {
+        self.addresses.push(address);
+        self.save()
+    }
+
+    pub fn get_all(&self) -> &[DerivedAddress] {
+        &self.addresses
+    }
+
+    /// Forget every derived address (the wallet was rebuilt from its seed).
+    pub fn clear(&mut self) -> Result<(), FaucetError> {
+        self.addresses.clear();
+        self.save()
+    }
+
+    /// Write to a temporary file and rename it over the old one, so a crash
+    /// mid-write cannot truncate the address book.
+    fn save(&self) -> Result<(), FaucetError> {
+        let json = serde_json::to_string_pretty(&self.addresses)
+            .map_err(|e| FaucetError::Internal(format!("Failed to serialize address book: {}", e)))?;
+
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, &self.file_path))
+            .map_err(|e| FaucetError::Internal(format!("Failed to write address book: {}", e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use crate::config::Config;
+use crate::shutdown::ShutdownSignal;
+use crate::wallet::SharedWallet;
+use tokio::time::Duration;
+use tracing::info;
+use zcash_protocol::value::Zatoshis;
+
+/// Policy for the background auto-shield task.
+///
+/// Coinbase from the continuous miner lands in the transparent pool, while
+/// `/request` and `/send` can only spend Orchard. Shielding whenever the
+/// confirmed transparent balance crosses `threshold` keeps the faucet spendable.
+#[derive(Debug, Clone)]
+pub struct AutoShieldPolicy {
+    pub threshold: Zatoshis,
+    pub interval: Duration,
+}
+
+impl AutoShieldPolicy {
+    /// Build the policy from config, or `None` when auto-shielding is disabled.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.auto_shield_enabled {
+            return None;
+        }
+
+        let threshold = Zatoshis::from_u64((config.auto_shield_threshold * 100_000_000.0) as u64)
+            .unwrap_or(Zatoshis::ZERO);
+
+        Some(Self {
+            threshold,
+            interval: Duration::from_secs(config.auto_shield_interval_secs.max(1)),
+        })
+    }
+}
+
+/// Periodically shield mature transparent funds to Orchard.
+///
+/// Zingolib only proposes spendable UTXOs, so immature coinbase is left alone
+/// and a proposal that finds nothing to shield is simply retried next tick.
+/// Each successful shield is recorded in the faucet history.
+pub async fn run(wallet: SharedWallet, policy: AutoShieldPolicy, mut shutdown: ShutdownSignal) {
+    info!(
+        "🛡 Starting auto-shield (threshold {} ZEC, every {}s)",
+        policy.threshold.into_u64() as f64 / 100_000_000.0,
+        policy.interval.as_secs()
+    );
+
+    let mut interval = tokio::time::interval(policy.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
+
+        let balance = match wallet.read().await.get_balance().await {
+            Ok(balance) => balance,
+            Err(e) => {
+                tracing::debug!("Auto-shield skipped - balance check failed: {}", e);
+                continue;
+            }
+        };
+
+        if balance.transparent == Zatoshis::ZERO || balance.transparent < policy.threshold {
+            tracing::debug!(
+                "Auto-shield: {} ZEC transparent is below threshold",
+                balance.transparent_zec()
+            );
+            continue;
+        }
+
+        // Don't queue behind a long sync or send; try again next tick
+        let lock_result = tokio::time::timeout(
+            Duration::from_secs(2),
+            wallet.write()
+        ).await;
+
+        let Ok(mut wallet_guard) = lock_result else {
+            tracing::debug!("⏭ Auto-shield skipped - couldn't acquire lock (wallet busy)");
+            continue;
+        };
+
+        match tokio::time::timeout(Duration::from_secs(120), wallet_guard.shield_to_orchard()).await {
+            Ok(Ok(txid)) => {
+                info!("🛡 Auto-shielded {} ZEC to Orchard in txid {}", balance.transparent_zec(), txid);
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("⚠ Auto-shield failed: {} (will retry)", e);
+            }
+            Err(_) => {
+                tracing::error!("⏱ Auto-shield timed out after 120s (will retry)");
+            }
+        }
+    }
+}
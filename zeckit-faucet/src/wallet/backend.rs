@@ -0,0 +1,258 @@
+use crate::config::{Indexer, IndexerKind};
+use crate::error::FaucetError;
+use crate::wallet::sync::SyncStatus;
+use crate::wallet::FaucetWallet;
+use axum::http::Uri;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration, Instant};
+use tonic::transport::Channel;
+use tracing::info;
+use zcash_client_backend::proto::service::compact_tx_streamer_client::CompactTxStreamerClient;
+use zcash_client_backend::proto::service::{ChainSpec, Empty};
+
+/// First retry delay while waiting for an indexer; doubles up to the max.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A single readiness probe gives up after this long.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The indexers the faucet can sync through, in order of preference, and
+/// which one it is using. Reported by `GET /admin/backend`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Backends {
+    pub indexers: Vec<Indexer>,
+    pub active: Indexer,
+    pub switch_count: u64,
+    pub last_switch: Option<DateTime<Utc>>,
+    pub last_switch_reason: Option<String>,
+}
+
+/// What an indexer reported about itself on its last probe.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexerInfo {
+    pub vendor: String,
+    pub version: String,
+    pub height: u64,
+}
+
+impl Backends {
+    pub fn new(indexers: Vec<Indexer>, active: Indexer) -> Self {
+        Self {
+            indexers,
+            active,
+            switch_count: 0,
+            last_switch: None,
+            last_switch_reason: None,
+        }
+    }
+
+    /// Find a configured indexer by URI, host name or kind (`zaino`,
+    /// `lightwalletd`).
+    pub fn resolve(&self, target: &str) -> Option<&Indexer> {
+        let target = target.trim().trim_end_matches('/');
+        let kind = target.parse::<IndexerKind>().ok();
+
+        self.indexers.iter().find(|indexer| {
+            indexer.uri.trim_end_matches('/') == target
+                || Some(indexer.kind) == kind
+                || indexer.uri.parse::<Uri>().ok().and_then(|u| u.host().map(|h| h == target)).unwrap_or(false)
+        })
+    }
+
+    /// The other indexers, in configured order, to try when the active one fails.
+    pub fn fallbacks(&self) -> Vec<Indexer> {
+        self.indexers.iter().filter(|indexer| **indexer != self.active).cloned().collect()
+    }
+
+    fn record_switch(&mut self, indexer: &Indexer, reason: &str) {
+        self.active = indexer.clone();
+        self.switch_count += 1;
+        self.last_switch = Some(Utc::now());
+        self.last_switch_reason = Some(reason.to_string());
+    }
+}
+
+/// Readiness probe that works for any `CompactTxStreamer` server: ask for
+/// the server's info and its latest block.
+pub async fn probe(indexer: &Indexer) -> Result<IndexerInfo, FaucetError> {
+    let uri = indexer.uri.clone();
+    let ping = async move {
+        let channel = Channel::from_shared(uri)?
+            .connect_timeout(Duration::from_secs(3))
+            .connect()
+            .await?;
+
+        let mut client = CompactTxStreamerClient::new(channel);
+        let info = client.get_lightd_info(Empty {}).await?.into_inner();
+        let block = client.get_latest_block(ChainSpec {}).await?.into_inner();
+
+        Ok::<IndexerInfo, anyhow::Error>(IndexerInfo {
+            vendor: info.vendor,
+            version: info.version,
+            height: block.height,
+        })
+    };
+
+    match tokio::time::timeout(PROBE_TIMEOUT, ping).await {
+        Ok(Ok(info)) => Ok(info),
+        Ok(Err(e)) => Err(FaucetError::Rpc(format!("{} at {} not reachable: {}", indexer.kind.name(), indexer.uri, e))),
+        Err(_) => Err(FaucetError::Rpc(format!("{} at {} timed out", indexer.kind.name(), indexer.uri))),
+    }
+}
+
+/// Wait for the first indexer that passes [`probe`], trying them in order
+/// with exponential backoff between rounds.
+pub async fn wait_ready(indexers: &[Indexer], timeout: Duration) -> anyhow::Result<(Indexer, IndexerInfo)> {
+    let start = Instant::now();
+    let mut delay = INITIAL_BACKOFF;
+    let mut last_error = None;
+
+    loop {
+        for indexer in indexers {
+            match probe(indexer).await {
+                Ok(info) => {
+                    warn_on_kind_mismatch(indexer, &info);
+                    info!(
+                        " {} at {} ready at block height {} (took {}s)",
+                        indexer.kind.name(), indexer.uri, info.height, start.elapsed().as_secs()
+                    );
+                    return Ok((indexer.clone(), info));
+                }
+                Err(e) => {
+                    tracing::debug!("Indexer not ready: {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        let remaining = timeout.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            break;
+        }
+
+        info!(" Still waiting for an indexer ({}s elapsed, retrying in {}s)", start.elapsed().as_secs(), delay.min(remaining).as_secs());
+        sleep(delay.min(remaining)).await;
+        delay = next_backoff(delay);
+    }
+
+    Err(anyhow::anyhow!(
+        "No indexer ready after {}s: {}",
+        timeout.as_secs(),
+        last_error.map(|e| e.to_string()).unwrap_or_default()
+    ))
+}
+
+fn next_backoff(delay: Duration) -> Duration {
+    (delay * 2).min(MAX_BACKOFF)
+}
+
+/// `INDEXER_KIND` is trusted, but a vendor string naming the other
+/// implementation usually means a misconfigured URI.
+fn warn_on_kind_mismatch(indexer: &Indexer, info: &IndexerInfo) {
+    let vendor = info.vendor.to_lowercase();
+    let other = match indexer.kind {
+        IndexerKind::Zaino => IndexerKind::Lightwalletd,
+        IndexerKind::Lightwalletd => IndexerKind::Zaino,
+    };
+    if vendor.contains(other.name()) {
+        tracing::warn!(
+            "⚠ {} is configured as {} but reports vendor {:?}",
+            indexer.uri, indexer.kind.name(), info.vendor
+        );
+    }
+}
+
+/// Point the wallet at another indexer.
+///
+/// The wallet is rebuilt from its seed and rescans from `from_height`, so
+/// everything it knows afterwards came through the new indexer; the caller
+/// runs the sync. Faucet history and derived addresses are kept.
+pub async fn switch(
+    wallet: &mut dyn FaucetWallet,
+    sync: &RwLock<SyncStatus>,
+    backends: &RwLock<Backends>,
+    indexer: &Indexer,
+    from_height: u32,
+    reason: &str,
+) -> Result<(), FaucetError> {
+    wallet.set_server_uri(&indexer.uri)?;
+    wallet.rebuild_from_seed(from_height, false).await?;
+    {
+        let mut sync = sync.write().await;
+        sync.wallet_height = None;
+        sync.orchard_zatoshis = None;
+    }
+    backends.write().await.record_switch(indexer, reason);
+
+    info!("🔀 Switched indexer to {} at {} ({})", indexer.kind.name(), indexer.uri, reason);
+    Ok(())
+}
+
+/// After a failed sync, move to the first fallback indexer that answers.
+/// Returns the indexer switched to, or `None` when there is nothing better.
+pub async fn failover(
+    wallet: &mut dyn FaucetWallet,
+    sync: &RwLock<SyncStatus>,
+    backends: &RwLock<Backends>,
+    error: &str,
+) -> Option<Indexer> {
+    let (active, fallbacks) = {
+        let backends = backends.read().await;
+        (backends.active.clone(), backends.fallbacks())
+    };
+
+    // A sync can fail for reasons unrelated to the indexer
+    if fallbacks.is_empty() || probe(&active).await.is_ok() {
+        return None;
+    }
+
+    for indexer in fallbacks {
+        match probe(&indexer).await {
+            Ok(_) => {
+                let reason = format!("failover from {}: {}", active.uri, error);
+                match switch(wallet, sync, backends, &indexer, 0, &reason).await {
+                    Ok(()) => return Some(indexer),
+                    Err(e) => tracing::warn!("⚠ Failover to {} failed: {}", indexer.uri, e),
+                }
+            }
+            Err(e) => tracing::debug!("Fallback indexer skipped: {}", e),
+        }
+    }
+
+    tracing::warn!("⚠ No other indexer is reachable; staying on {}", active.uri);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexer(uri: &str, kind: IndexerKind) -> Indexer {
+        Indexer { uri: uri.to_string(), kind }
+    }
+
+    #[test]
+    fn test_resolves_backends_by_uri_host_or_kind() {
+        let zaino = indexer("http://zaino:9067", IndexerKind::Zaino);
+        let lwd = indexer("http://lightwalletd:9067", IndexerKind::Lightwalletd);
+        let backends = Backends::new(vec![zaino.clone(), lwd.clone()], zaino.clone());
+
+        assert_eq!(backends.resolve("lightwalletd"), Some(&lwd));
+        assert_eq!(backends.resolve("lwd"), Some(&lwd));
+        assert_eq!(backends.resolve("http://zaino:9067/"), Some(&zaino));
+        assert_eq!(backends.resolve("zebra"), None);
+        assert_eq!(backends.fallbacks(), vec![lwd]);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        let delays: Vec<u64> = std::iter::successors(Some(INITIAL_BACKOFF), |d| Some(next_backoff(*d)))
+            .take(7)
+            .map(|d| d.as_secs())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30]);
+    }
+}
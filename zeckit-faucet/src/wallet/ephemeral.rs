@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// A throwaway test wallet: a fresh random mnemonic and the addresses a test
+/// needs to receive on it. The faucet keeps no copy of it once derived.
+#[derive(Debug, Clone, Serialize)]
+pub struct EphemeralWallet {
+    pub mnemonic: String,
+    pub birthday: u32,
+    pub unified_address: String,
+    /// Bare Sapling address (`zregtestsapling…`), so funds sent to it land
+    /// in Sapling.
+    pub sapling_address: String,
+    pub transparent_address: String,
+}
@@ -0,0 +1,138 @@
+use crate::error::FaucetError;
+use crate::wallet::accounts::{AccountRecord, AccountRegistry};
+use crate::wallet::addresses::{DerivedAddress, Receivers};
+use crate::wallet::ephemeral::EphemeralWallet;
+use crate::wallet::history::{TransactionHistory, TransactionKind, TransactionRecord};
+use crate::wallet::manager::Balance;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+use zcash_primitives::memo::MemoBytes;
+
+/// The wallet as the HTTP handlers and background tasks share it.
+pub type SharedWallet = Arc<RwLock<dyn FaucetWallet>>;
+
+/// Everything the faucet needs from a wallet.
+///
+/// [`crate::wallet::WalletManager`] implements it on top of zingolib; tests
+/// use the in-memory [`crate::wallet::mock::MockWallet`], so routes can be
+/// exercised without a chain or an indexer.
+#[async_trait]
+pub trait FaucetWallet: Send + Sync {
+    /// First unified address of the default account.
+    async fn get_unified_address(&self) -> Result<String, FaucetError>;
+
+    /// First transparent address of the default account.
+    async fn get_transparent_address(&self) -> Result<String, FaucetError>;
+
+    /// First unified address of the given account.
+    async fn get_account_address(&self, account: zip32::AccountId) -> Result<String, FaucetError>;
+
+    /// Derive fresh addresses for `account`: one diversified unified address
+    /// with the requested shielded receivers, and a new transparent address
+    /// when `transparent` is requested.
+    async fn new_address(
+        &mut self,
+        receivers: Receivers,
+        account: zip32::AccountId,
+    ) -> Result<Vec<DerivedAddress>, FaucetError>;
+
+    /// Addresses derived through [`FaucetWallet::new_address`].
+    fn derived_addresses(&self) -> &[DerivedAddress];
+
+    /// Total zatoshis received per address, over spent and unspent funds.
+    async fn received_by_address(&self) -> HashMap<String, u64>;
+
+    /// Generate a throwaway wallet from a random mnemonic and derive its
+    /// addresses. The faucet keeps no copy of it.
+    async fn derive_ephemeral(&self, birthday: u32) -> Result<EphemeralWallet, FaucetError>;
+
+    fn accounts(&self) -> &AccountRegistry;
+
+    /// Register a named sub-account and derive its keys in the running
+    /// wallet, without a rescan.
+    async fn create_account(
+        &mut self,
+        name: &str,
+        api_key: Option<String>,
+    ) -> Result<AccountRecord, FaucetError>;
+
+    /// Unified full and incoming viewing keys of `account`, encoded for regtest.
+    async fn viewing_keys(&self, account: zip32::AccountId) -> Result<(String, String), FaucetError>;
+
+    /// Height the wallet scans from; importing wallets can start there.
+    async fn birthday(&self) -> u32;
+
+    /// The mnemonic this wallet was built from.
+    fn seed_phrase(&self) -> Option<String>;
+
+    async fn get_account_balance(&self, account: zip32::AccountId) -> Result<Balance, FaucetError>;
+
+    async fn get_balance(&self) -> Result<Balance, FaucetError> {
+        self.get_account_balance(zip32::AccountId::ZERO).await
+    }
+
+    /// Shield the default account's transparent funds to Orchard and record
+    /// it in the history. Returns the txid.
+    async fn shield_to_orchard(&mut self) -> Result<String, FaucetError>;
+
+    /// Pay every `(address, amount_zec, memo)` in a single transaction from
+    /// `account`'s Orchard funds and record each payment in the history.
+    async fn send_payments(
+        &mut self,
+        payments: Vec<(String, f64, Option<MemoBytes>)>,
+        account: zip32::AccountId,
+    ) -> Result<String, FaucetError>;
+
+    async fn send_transaction(
+        &mut self,
+        to_address: &str,
+        amount_zec: f64,
+        memo_bytes: Option<MemoBytes>,
+        account: zip32::AccountId,
+    ) -> Result<String, FaucetError> {
+        info!("Sending {} ZEC to {}", amount_zec, &to_address[..to_address.len().min(16)]);
+
+        self.send_payments(vec![(to_address.to_string(), amount_zec, memo_bytes)], account).await
+    }
+
+    async fn sync(&mut self) -> Result<(), FaucetError>;
+
+    /// Height up to which the wallet has fully scanned the chain.
+    async fn synced_height(&self) -> Option<u64>;
+
+    /// Replace the wallet with one rebuilt from the seed, so the next sync
+    /// rescans from `from_height`. With `clear_history`, the faucet's
+    /// send/shield history and derived addresses are wiped as well.
+    async fn rebuild_from_seed(&mut self, from_height: u32, clear_history: bool) -> Result<(), FaucetError>;
+
+    /// Use another indexer from the next rebuild on; see
+    /// [`crate::wallet::backend::switch`].
+    fn set_server_uri(&mut self, uri: &str) -> Result<(), FaucetError>;
+
+    /// Write the wallet and the faucet history to disk before shutting down.
+    async fn persist(&mut self) -> Result<(), FaucetError>;
+
+    fn history(&self) -> &TransactionHistory;
+
+    /// Most recent entries first, optionally only those of one account.
+    fn get_transaction_history(&self, limit: usize, account: Option<u32>) -> Vec<TransactionRecord> {
+        match account {
+            Some(index) => self.history().get_recent_for_account(index, limit),
+            None => self.history().get_recent(limit),
+        }
+    }
+
+    /// Count and total of outgoing sends; shields are internal and excluded.
+    fn get_stats(&self) -> (usize, f64) {
+        let sends: Vec<_> = self.history().get_all()
+            .iter()
+            .filter(|tx| tx.kind == TransactionKind::Send)
+            .collect();
+        let count = sends.len();
+        let total_sent: f64 = sends.iter().map(|tx| tx.amount).sum();
+        (count, total_sent)
+    }
+}
@@ -4,13 +4,31 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// What a history entry records. Entries written before shields were
+/// tracked have no `kind` and are treated as sends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionKind {
+    #[default]
+    Send,
+    Shield,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionRecord {
+    #[serde(default)]
+    pub kind: TransactionKind,
+    /// ZIP-32 account the funds came from; 0 is the faucet's own account.
+    #[serde(default)]
+    pub account: u32,
     pub timestamp: DateTime<Utc>,
     pub to_address: String,
     pub amount: f64,
     pub txid: String,
     pub memo: String,
+    /// Hex of the 512-byte memo field as sent; absent when no memo was attached.
+    #[serde(default)]
+    pub memo_hex: Option<String>,
 }
 
 pub struct TransactionHistory {
@@ -44,11 +62,25 @@ impl TransactionHistory {
         Ok(())
     }
 
+    pub fn clear(&mut self) -> Result<(), FaucetError> {
+        self.transactions.clear();
+        self.save()
+    }
+
+    /// Write the history to disk again, e.g. before shutting down.
+    pub fn flush(&self) -> Result<(), FaucetError> {
+        self.save()
+    }
+
+    /// Write to a temporary file and rename it over the old one, so a crash
+    /// mid-write never leaves a truncated history behind.
     fn save(&self) -> Result<(), FaucetError> {
         let json = serde_json::to_string_pretty(&self.transactions)
             .map_err(|e| FaucetError::Internal(format!("Failed to serialize history: {}", e)))?;
         
-        fs::write(&self.file_path, json)
+        let tmp_path = self.file_path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)
+            .and_then(|_| fs::rename(&tmp_path, &self.file_path))
             .map_err(|e| FaucetError::Internal(format!("Failed to write history: {}", e)))?;
         
         Ok(())
@@ -66,4 +98,14 @@ impl TransactionHistory {
             .cloned()
             .collect()
     }
+
+    pub fn get_recent_for_account(&self, account: u32, limit: usize) -> Vec<TransactionRecord> {
+        self.transactions
+            .iter()
+            .rev()
+            .filter(|tx| tx.account == account)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
 }
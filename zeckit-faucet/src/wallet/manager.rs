@@ -1,20 +1,32 @@
 use crate::error::FaucetError;
-use crate::wallet::history::{TransactionHistory, TransactionRecord};
-use std::path::PathBuf;
+use crate::config::ActivationHeights;
+use crate::wallet::faucet_wallet::FaucetWallet;
+use crate::wallet::history::{TransactionHistory, TransactionKind, TransactionRecord};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tracing::info;
 use zingolib::{
     lightclient::LightClient,
     config::{ZingoConfig, ChainType},
-    wallet::{LightWallet, WalletBase},
+    wallet::{LightWallet, WalletBase, keys::unified::{ReceiverSelection, UnifiedKeyStore}},
 };
 use axum::http::Uri;
 use zcash_primitives::consensus::BlockHeight;
 use zebra_chain::parameters::testnet::ConfiguredActivationHeights;
 use zcash_primitives::memo::MemoBytes;
 use zcash_client_backend::zip321::{TransactionRequest, Payment};
-use crate::wallet::seed::SeedManager;
+use zcash_keys::keys::{UnifiedFullViewingKey, UnifiedSpendingKey};
+use crate::wallet::seed::{self, SeedManager};
+use crate::wallet::ephemeral::EphemeralWallet;
+use crate::wallet::accounts::{AccountRecord, AccountRegistry};
+use crate::wallet::addresses::{sapling_receiver, AddressBook, AddressKind, DerivedAddress, Receivers};
+use crate::wallet::memo;
 use zcash_protocol::value::Zatoshis;
 
+/// Zingolib's wallet file inside the data directory.
+const WALLET_FILE: &str = "zingo-wallet.dat";
+
 #[derive(Debug, Clone)]
 pub struct Balance {
     pub transparent: Zatoshis,
@@ -48,12 +60,18 @@ impl Balance {
 pub struct WalletManager {
     client: LightClient,
     history: TransactionHistory,
+    accounts: AccountRegistry,
+    address_book: AddressBook,
+    data_dir: PathBuf,
+    server_uri: Uri,
+    activation: ActivationHeights,
 }
 
 impl WalletManager {
     pub async fn new(
         data_dir: PathBuf,
         server_uri: String,
+        activation: ActivationHeights,
     ) -> Result<Self, FaucetError> {
         info!("Initializing ZingoLib LightClient");
         
@@ -65,12 +83,44 @@ impl WalletManager {
             FaucetError::Wallet(format!("Failed to create wallet directory: {}", e))
         })?;
 
-        // ============================================================
-        // NEW: Get or create deterministic seed
-        // ============================================================
+        let (chain_type, config) = Self::regtest_config(&data_dir, uri.clone(), activation);
+        let accounts = AccountRegistry::load(&data_dir)?;
+
+        let wallet_path = data_dir.join(WALLET_FILE);
+
+        // A wallet file built from a different seed would mine to one address
+        // and spend from another, so a seed change forces a rebuild
         let seed_manager = SeedManager::new(&data_dir);
+        let previous_seed = seed_manager.stored_seed();
         let seed_phrase = seed_manager.get_or_create_seed()?;
+        let seed_changed = previous_seed.is_some_and(|previous| previous != seed_phrase);
         
+        // ============================================================
+        // Load existing wallet or create new one from the configured seed
+        // ============================================================
+        let client = if wallet_path.exists() && !seed_changed {
+            info!("Loading existing wallet from {:?}", wallet_path);
+            LightClient::create_from_wallet_path(config).map_err(|e| {
+                FaucetError::Wallet(format!("Failed to load wallet: {}", e))
+            })?
+        } else {
+            if seed_changed {
+                info!("Wallet seed changed - rebuilding wallet from the new seed");
+            } else {
+                info!("Creating new wallet from seed");
+            }
+            Self::client_from_seed(&data_dir, chain_type, config, 0, accounts.account_count(), seed_changed)?
+        };
+
+        let history = TransactionHistory::load(&data_dir)?;
+        let address_book = AddressBook::load(&data_dir)?;
+
+        info!("Wallet initialized successfully (sync not started)");
+
+        Ok(Self { client, history, accounts, address_book, data_dir, server_uri: uri, activation })
+    }
+
+    fn regtest_config(data_dir: &Path, uri: Uri, activation: ActivationHeights) -> (ChainType, ZingoConfig) {
         let activation_heights = ConfiguredActivationHeights {
             before_overwinter: Some(1),
             overwinter: Some(1),
@@ -78,62 +128,179 @@ impl WalletManager {
             blossom: Some(1),
             heartwood: Some(1),
             canopy: Some(1),
-            nu5: Some(1),
-            nu6: None,      // ← Changed to None
-            nu6_1: None,    // ← Changed to None
-            nu7: None,      // ← Changed to None
+            nu5: Some(activation.nu5),
+            nu6: activation.nu6,
+            nu6_1: activation.nu6_1,
+            nu7: activation.nu7,
         };
         let chain_type = ChainType::Regtest(activation_heights);
         
         let config = ZingoConfig::build(chain_type)
             .set_lightwalletd_uri(uri)
-            .set_wallet_dir(data_dir.clone())
+            .set_wallet_dir(data_dir.to_path_buf())
             .create();
 
-        let wallet_path = data_dir.join("zingo-wallet.dat");
+        (chain_type, config)
+    }
+
+    /// Write the zingolib wallet file through a temporary file, so a crash
+    /// mid-write leaves the previous file in place.
+    async fn save_wallet(&self) -> Result<(), FaucetError> {
+        let bytes = self.client.wallet.write().await.save().map_err(|e| {
+            FaucetError::Wallet(format!("Failed to serialize wallet: {}", e))
+        })?;
+
+        // `None` means nothing changed since the last save
+        if let Some(bytes) = bytes {
+            let path = self.data_dir.join(WALLET_FILE);
+            let tmp_path = path.with_extension("dat.tmp");
+            std::fs::write(&tmp_path, bytes)
+                .and_then(|_| std::fs::rename(&tmp_path, &path))
+                .map_err(|e| FaucetError::Wallet(format!("Failed to write wallet file: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Build a fresh client from the seed held by `SeedManager`, scanning
+    /// from `birthday` and deriving `no_of_accounts` ZIP-32 accounts.
+    /// With `overwrite`, any existing wallet file is replaced.
+    fn client_from_seed(
+        data_dir: &Path,
+        chain_type: ChainType,
+        config: ZingoConfig,
+        birthday: u32,
+        no_of_accounts: u32,
+        overwrite: bool,
+    ) -> Result<LightClient, FaucetError> {
+        let seed_manager = SeedManager::new(data_dir);
+        let seed_phrase = seed_manager.get_or_create_seed()?;
+
+        Self::client_from_mnemonic(&seed_phrase, chain_type, config, birthday, no_of_accounts, overwrite)
+    }
+
+    fn client_from_mnemonic(
+        seed_phrase: &str,
+        chain_type: ChainType,
+        config: ZingoConfig,
+        birthday: u32,
+        no_of_accounts: u32,
+        overwrite: bool,
+    ) -> Result<LightClient, FaucetError> {
+        // Convert seed phrase string to Mnemonic
+        let mnemonic = bip0039::Mnemonic::from_phrase(seed_phrase)
+            .map_err(|e| FaucetError::Wallet(format!("Invalid mnemonic phrase: {}", e)))?;
         
-        // ============================================================
-        // Load existing wallet or create new one with deterministic seed
-        // ============================================================
-        let client = if wallet_path.exists() {
-            info!("Loading existing wallet from {:?}", wallet_path);
-            LightClient::create_from_wallet_path(config).map_err(|e| {
-                FaucetError::Wallet(format!("Failed to load wallet: {}", e))
-            })?
-        } else {
-            info!("Creating new wallet with deterministic seed");
-            
-            // Convert seed phrase string to Mnemonic
-            let mnemonic = bip0039::Mnemonic::from_phrase(seed_phrase)
-                .map_err(|e| FaucetError::Wallet(format!("Invalid mnemonic phrase: {}", e)))?;
-            
-            // Create wallet from mnemonic
-            let wallet = LightWallet::new(
-                chain_type,
-                WalletBase::Mnemonic {
-                    mnemonic,
-                    no_of_accounts: std::num::NonZeroU32::new(1).unwrap(),
-                },
-                BlockHeight::from_u32(0),
-                config.wallet_settings.clone(),
-            ).map_err(|e| {
-                FaucetError::Wallet(format!("Failed to create wallet: {}", e))
-            })?;
-            
-            // Create LightClient from the wallet
-            LightClient::create_from_wallet(wallet, config, false).map_err(|e| {
-                FaucetError::Wallet(format!("Failed to create client from wallet: {}", e))
-            })?
-        };
+        // Create wallet from mnemonic
+        let wallet = LightWallet::new(
+            chain_type,
+            WalletBase::Mnemonic {
+                mnemonic,
+                no_of_accounts: std::num::NonZeroU32::new(no_of_accounts.max(1)).unwrap(),
+            },
+            BlockHeight::from_u32(birthday),
+            config.wallet_settings.clone(),
+        ).map_err(|e| {
+            FaucetError::Wallet(format!("Failed to create wallet: {}", e))
+        })?;
+        
+        // Create LightClient from the wallet
+        LightClient::create_from_wallet(wallet, config, overwrite).map_err(|e| {
+            FaucetError::Wallet(format!("Failed to create client from wallet: {}", e))
+        })
+    }
+}
 
-        let history = TransactionHistory::load(&data_dir)?;
+#[async_trait]
+impl FaucetWallet for WalletManager {
+    /// Replace the zingolib wallet with one rebuilt from the seed, so the
+    /// next sync rescans from `from_height`. Zebra's chain state is untouched.
+    /// With `clear_history`, the faucet's send/shield history is wiped as well.
+    async fn rebuild_from_seed(
+        &mut self,
+        from_height: u32,
+        clear_history: bool,
+    ) -> Result<(), FaucetError> {
+        info!("Rebuilding wallet from seed (birthday {})", from_height);
+
+        let (chain_type, config) = Self::regtest_config(&self.data_dir, self.server_uri.clone(), self.activation);
+        self.client = Self::client_from_seed(
+            &self.data_dir,
+            chain_type,
+            config,
+            from_height,
+            self.accounts.account_count(),
+            true,
+        )?;
+
+        if clear_history {
+            self.history.clear()?;
+            self.address_book.clear()?;
+        }
 
-        info!("Wallet initialized successfully (sync not started)");
+        info!("Wallet rebuilt; next sync rescans from height {}", from_height);
+        Ok(())
+    }
+
+    /// Use another indexer from the next rebuild on; see
+    /// [`crate::wallet::backend::switch`].
+    fn set_server_uri(&mut self, uri: &str) -> Result<(), FaucetError> {
+        self.server_uri = uri.parse().map_err(|e| {
+            FaucetError::Validation(format!("Invalid indexer URI {}: {}", uri, e))
+        })?;
+        Ok(())
+    }
+
+    fn accounts(&self) -> &AccountRegistry {
+        &self.accounts
+    }
+
+    /// Register a named sub-account and add its keys to the running wallet.
+    /// A new account cannot have received anything yet, so nothing is
+    /// rescanned and the other accounts keep their balances.
+    async fn create_account(
+        &mut self,
+        name: &str,
+        api_key: Option<String>,
+    ) -> Result<AccountRecord, FaucetError> {
+        self.accounts.validate(name, api_key.as_deref())?;
+        let index = self.accounts.account_count();
+        let account = zip32::AccountId::try_from(index)
+            .map_err(|_| FaucetError::Validation(format!("Invalid account index: {}", index)))?;
+
+        let seed_phrase = SeedManager::new(&self.data_dir).get_or_create_seed()?;
+        let mnemonic = bip0039::Mnemonic::from_phrase(&seed_phrase)
+            .map_err(|e| FaucetError::Wallet(format!("Invalid mnemonic phrase: {}", e)))?;
+        {
+            let mut wallet = self.client.wallet.write().await;
+            let usk = UnifiedSpendingKey::from_seed(&wallet.network, &mnemonic.to_seed(""), account)
+                .map_err(|e| FaucetError::Wallet(format!("Failed to derive account keys: {:?}", e)))?;
+            wallet.unified_key_store.insert(account, UnifiedKeyStore::Spend(Box::new(usk)));
+        }
+        self.client
+            .generate_unified_address(ReceiverSelection { orchard: true, sapling: true }, account)
+            .await
+            .map_err(|e| FaucetError::Wallet(format!("Failed to derive unified address: {}", e)))?;
+
+        let record = self.accounts.add(name, api_key)?;
+        self.save_wallet().await?;
+        info!("Created account {:?} (ZIP-32 account {})", record.name, record.index);
+        Ok(record)
+    }
 
-        Ok(Self { client, history })
+    /// First unified address of the given account.
+    async fn get_account_address(&self, account: zip32::AccountId) -> Result<String, FaucetError> {
+        let addresses_json = self.client.unified_addresses_json().await;
+        let index = u32::from(account) as u64;
+
+        addresses_json
+            .members()
+            .find(|address| address["account"].as_u64().unwrap_or(0) == index)
+            .and_then(|address| address["encoded_address"].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| FaucetError::Wallet(format!("No unified address for account {}", index)))
     }
 
-    pub async fn get_unified_address(&self) -> Result<String, FaucetError> {
+    async fn get_unified_address(&self) -> Result<String, FaucetError> {
         let addresses_json = self.client.unified_addresses_json().await;
         
         let first_address = addresses_json[0]["encoded_address"]
@@ -143,7 +310,183 @@ impl WalletManager {
         Ok(first_address.to_string())
     }
 
-    pub async fn get_transparent_address(&self) -> Result<String, FaucetError> {
+    /// Derive fresh addresses for `account`: one diversified unified address
+    /// with the requested shielded receivers, and a new transparent address
+    /// when `transparent` is requested.
+    async fn new_address(
+        &mut self,
+        receivers: Receivers,
+        account: zip32::AccountId,
+    ) -> Result<Vec<DerivedAddress>, FaucetError> {
+        let mut derived = Vec::new();
+
+        if receivers.has_shielded() {
+            let selection = ReceiverSelection {
+                orchard: receivers.orchard,
+                sapling: receivers.sapling,
+            };
+            self.client
+                .generate_unified_address(selection, account)
+                .await
+                .map_err(|e| FaucetError::Wallet(format!("Failed to derive unified address: {}", e)))?;
+
+            let addresses_json = self.client.unified_addresses_json().await;
+            let address = addresses_json[addresses_json.len().saturating_sub(1)]["encoded_address"]
+                .as_str()
+                .ok_or_else(|| FaucetError::Wallet("Derived unified address not found".to_string()))?;
+
+            derived.push(DerivedAddress {
+                address: address.to_string(),
+                kind: AddressKind::Unified,
+                receivers: Receivers { transparent: false, ..receivers }.names(),
+                account: u32::from(account),
+                created_at: chrono::Utc::now(),
+            });
+
+            // zingolib only hands out unified addresses; a Sapling-only one
+            // carries exactly the receiver a bare Sapling address encodes
+            if receivers.sapling && !receivers.orchard {
+                let sapling = sapling_receiver(address)
+                    .ok_or_else(|| FaucetError::Wallet("Derived address has no Sapling receiver".to_string()))?;
+                derived.push(DerivedAddress {
+                    address: sapling,
+                    kind: AddressKind::Sapling,
+                    receivers: vec!["sapling".to_string()],
+                    account: u32::from(account),
+                    created_at: chrono::Utc::now(),
+                });
+            }
+        }
+
+        if receivers.transparent {
+            self.client
+                .generate_transparent_address(account, false)
+                .await
+                .map_err(|e| FaucetError::Wallet(format!("Failed to derive transparent address: {}", e)))?;
+
+            let addresses_json = self.client.transparent_addresses_json().await;
+            let address = addresses_json[addresses_json.len().saturating_sub(1)]["encoded_address"]
+                .as_str()
+                .ok_or_else(|| FaucetError::Wallet("Derived transparent address not found".to_string()))?;
+
+            derived.push(DerivedAddress {
+                address: address.to_string(),
+                kind: AddressKind::Transparent,
+                receivers: vec!["transparent".to_string()],
+                account: u32::from(account),
+                created_at: chrono::Utc::now(),
+            });
+        }
+
+        for address in &derived {
+            info!("Derived {:?} address {}", address.kind, &address.address[..address.address.len().min(20)]);
+            self.address_book.add(address.clone())?;
+        }
+
+        Ok(derived)
+    }
+
+    fn derived_addresses(&self) -> &[DerivedAddress] {
+        self.address_book.get_all()
+    }
+
+    /// Total zatoshis received per address, over spent and unspent notes and UTXOs.
+    async fn received_by_address(&self) -> HashMap<String, u64> {
+        let notes = self.client.do_list_notes(true).await;
+        let mut totals: HashMap<String, u64> = HashMap::new();
+
+        for pool in [
+            "unspent_orchard_notes", "spent_orchard_notes", "pending_orchard_notes",
+            "unspent_sapling_notes", "spent_sapling_notes", "pending_sapling_notes",
+            "utxos", "spent_utxos", "pending_utxos",
+        ] {
+            for note in notes[pool].members() {
+                if let (Some(address), Some(value)) = (note["address"].as_str(), note["value"].as_u64()) {
+                    *totals.entry(address.to_string()).or_default() += value;
+                }
+            }
+        }
+
+        totals
+    }
+
+    /// Generate a throwaway wallet from a random mnemonic and derive its
+    /// addresses. It is built in a scratch directory that is removed again;
+    /// only the mnemonic and addresses are returned.
+    async fn derive_ephemeral(&self, birthday: u32) -> Result<EphemeralWallet, FaucetError> {
+        let mnemonic = seed::generate_mnemonic();
+
+        let scratch_dir = self.data_dir
+            .join("ephemeral")
+            .join(chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default().to_string());
+        std::fs::create_dir_all(&scratch_dir).map_err(|e| {
+            FaucetError::Wallet(format!("Failed to create scratch wallet directory: {}", e))
+        })?;
+
+        let derived = async {
+            let (chain_type, config) = Self::regtest_config(&scratch_dir, self.server_uri.clone(), self.activation);
+            let mut client = Self::client_from_mnemonic(&mnemonic, chain_type, config, birthday, 1, true)?;
+
+            let unified_address = client.unified_addresses_json().await[0]["encoded_address"]
+                .as_str()
+                .ok_or_else(|| FaucetError::Wallet("No unified address found".to_string()))?
+                .to_string();
+            let transparent_address = client.transparent_addresses_json().await[0]["encoded_address"]
+                .as_str()
+                .ok_or_else(|| FaucetError::Wallet("No transparent address found".to_string()))?
+                .to_string();
+
+            client
+                .generate_unified_address(ReceiverSelection { orchard: false, sapling: true }, zip32::AccountId::ZERO)
+                .await
+                .map_err(|e| FaucetError::Wallet(format!("Failed to derive Sapling address: {}", e)))?;
+            let addresses_json = client.unified_addresses_json().await;
+            // Hand out the bare Sapling address, as `/address/new` does
+            let sapling_address = addresses_json[addresses_json.len().saturating_sub(1)]["encoded_address"]
+                .as_str()
+                .and_then(sapling_receiver)
+                .ok_or_else(|| FaucetError::Wallet("Derived Sapling address not found".to_string()))?;
+
+            Ok::<_, FaucetError>((unified_address, sapling_address, transparent_address))
+        }
+        .await;
+
+        let _ = std::fs::remove_dir_all(&scratch_dir);
+        let (unified_address, sapling_address, transparent_address) = derived?;
+
+        Ok(EphemeralWallet {
+            mnemonic,
+            birthday,
+            unified_address,
+            sapling_address,
+            transparent_address,
+        })
+    }
+
+    /// Unified full and incoming viewing keys of `account`, encoded for regtest.
+    async fn viewing_keys(&self, account: zip32::AccountId) -> Result<(String, String), FaucetError> {
+        let wallet = self.client.wallet.read().await;
+
+        let key_store = wallet.unified_key_store.get(&account)
+            .ok_or_else(|| FaucetError::Wallet(format!("No keys for account {}", u32::from(account))))?;
+        let ufvk = UnifiedFullViewingKey::try_from(key_store)
+            .map_err(|e| FaucetError::Wallet(format!("Failed to derive viewing key: {}", e)))?;
+
+        let uivk = ufvk.to_unified_incoming_viewing_key();
+        Ok((ufvk.encode(&wallet.network), uivk.encode(&wallet.network)))
+    }
+
+    /// Height the wallet scans from; importing wallets can start there.
+    async fn birthday(&self) -> u32 {
+        u32::from(self.client.wallet.read().await.birthday)
+    }
+
+    /// The mnemonic this wallet was built from.
+    fn seed_phrase(&self) -> Option<String> {
+        SeedManager::new(&self.data_dir).stored_seed()
+    }
+
+    async fn get_transparent_address(&self) -> Result<String, FaucetError> {
         let addresses_json = self.client.transparent_addresses_json().await;
         
         let first_address = addresses_json[0]["encoded_address"]
@@ -153,9 +496,9 @@ impl WalletManager {
         Ok(first_address.to_string())
     }
 
-    pub async fn get_balance(&self) -> Result<Balance, FaucetError> {
+    async fn get_account_balance(&self, account: zip32::AccountId) -> Result<Balance, FaucetError> {
         let account_balance = self.client
-            .account_balance(zip32::AccountId::ZERO)
+            .account_balance(account)
             .await
             .map_err(|e| FaucetError::Wallet(format!("Failed to get balance: {}", e)))?;
         
@@ -169,7 +512,7 @@ impl WalletManager {
         })
     }
 
-    pub async fn shield_to_orchard(&mut self) -> Result<String, FaucetError> {
+    async fn shield_to_orchard(&mut self) -> Result<String, FaucetError> {
         info!("Shielding transparent funds to Orchard...");
         
         let balance = self.get_balance().await?;
@@ -195,71 +538,71 @@ impl WalletManager {
         let txid = txids.first().to_string();
         
         info!("Shielded transparent funds in txid: {}", txid);
+
+        let to_address = self.get_unified_address().await?;
+        self.history.add_transaction(TransactionRecord {
+            kind: TransactionKind::Shield,
+            account: 0,
+            txid: txid.clone(),
+            to_address,
+            amount: balance.transparent_zec(),
+            timestamp: chrono::Utc::now(),
+            memo: String::new(),
+            memo_hex: None,
+        })?;
+
         Ok(txid)
     }
 
-    pub async fn send_transaction(
+    /// Pay every `(address, amount_zec, memo)` in a single transaction from
+    /// `account`'s Orchard funds, so no payment waits on another's change.
+    async fn send_payments(
         &mut self,
-        to_address: &str,
-        amount_zec: f64,
-        memo: Option<String>,
+        payments: Vec<(String, f64, Option<MemoBytes>)>,
+        account: zip32::AccountId,
     ) -> Result<String, FaucetError> {
-        info!("Sending {} ZEC to {}", amount_zec, &to_address[..to_address.len().min(16)]);
-
-        let amount_zatoshis = (amount_zec * 100_000_000.0) as u64;
+        let total_zec: f64 = payments.iter().map(|(_, amount, _)| amount).sum();
+        let total_zatoshis = (total_zec * 100_000_000.0) as u64;
 
-        let balance = self.get_balance().await?;
-        if balance.orchard < Zatoshis::from_u64(amount_zatoshis).unwrap() {
+        let balance = self.get_account_balance(account).await?;
+        if balance.orchard < Zatoshis::from_u64(total_zatoshis).unwrap() {
             return Err(FaucetError::InsufficientBalance(format!(
                 "Need {} ZEC, have {} ZEC in Orchard pool",
-                amount_zec,
+                total_zec,
                 balance.orchard_zec()
             )));
         }
 
-        // Parse recipient address
-        let recipient_address = to_address.parse()
-            .map_err(|e| FaucetError::Wallet(format!("Invalid address: {}", e)))?;
-
-        // Create amount
-        let amount = zcash_protocol::value::Zatoshis::from_u64(amount_zatoshis)
-            .map_err(|_| FaucetError::Wallet("Invalid amount".to_string()))?;
-
-        // Create memo bytes if provided
-        let memo_bytes = if let Some(memo_text) = &memo {
-            // Convert string to bytes (max 512 bytes for Zcash memo)
-            let bytes = memo_text.as_bytes();
-            if bytes.len() > 512 {
-                return Err(FaucetError::Wallet("Memo too long (max 512 bytes)".to_string()));
-            }
-            
-            // Pad to 512 bytes
-            let mut padded = [0u8; 512];
-            padded[..bytes.len()].copy_from_slice(bytes);
-            
-            Some(MemoBytes::from_bytes(&padded)
-                .map_err(|e| FaucetError::Wallet(format!("Invalid memo: {}", e)))?)
-        } else {
-            None
-        };
-
-        // Create Payment with all 6 required arguments
-        let payment = Payment::new(
-            recipient_address,
-            amount,
-            memo_bytes,
-            None,  // label
-            None,  // message
-            vec![], // other_params
-        ).ok_or_else(|| FaucetError::Wallet("Failed to create payment".to_string()))?;
+        let mut zip321_payments = Vec::with_capacity(payments.len());
+        for (to_address, amount_zec, memo_bytes) in &payments {
+            // Parse recipient address
+            let recipient_address = to_address.parse()
+                .map_err(|e| FaucetError::Wallet(format!("Invalid address: {}", e)))?;
+
+            // Create amount
+            let amount = zcash_protocol::value::Zatoshis::from_u64((amount_zec * 100_000_000.0) as u64)
+                .map_err(|_| FaucetError::Wallet("Invalid amount".to_string()))?;
+
+            // Create Payment with all 6 required arguments
+            let payment = Payment::new(
+                recipient_address,
+                amount,
+                memo_bytes.clone(),
+                None,  // label
+                None,  // message
+                vec![], // other_params
+            ).ok_or_else(|| FaucetError::Wallet("Failed to create payment".to_string()))?;
+
+            zip321_payments.push(payment);
+        }
 
         // Create TransactionRequest
-        let request = TransactionRequest::new(vec![payment])
+        let request = TransactionRequest::new(zip321_payments)
             .map_err(|e| FaucetError::Wallet(format!("Failed to create request: {}", e)))?;
 
         // Send using quick_send
         let txids = self.client
-            .quick_send(request, zip32::AccountId::ZERO, false)
+            .quick_send(request, account, false)
             .await
             .map_err(|e| {
                 FaucetError::TransactionFailed(format!("Failed to send transaction: {}", e))
@@ -267,33 +610,53 @@ impl WalletManager {
 
         let txid = txids.first().to_string();
 
-        // Record in history
-        self.history.add_transaction(TransactionRecord {
-            txid: txid.clone(),
-            to_address: to_address.to_string(),
-            amount: amount_zec,
-            timestamp: chrono::Utc::now(),
-            memo: memo.unwrap_or_default(),
-        })?;
+        // Record in history, keeping the exact memo bytes that were sent
+        for (to_address, amount_zec, memo_bytes) in payments {
+            self.history.add_transaction(TransactionRecord {
+                kind: TransactionKind::Send,
+                account: u32::from(account),
+                txid: txid.clone(),
+                to_address,
+                amount: amount_zec,
+                timestamp: chrono::Utc::now(),
+                memo: memo_bytes.as_ref().map(memo::memo_text).unwrap_or_default(),
+                memo_hex: memo_bytes.as_ref().map(memo::memo_hex),
+            })?;
+        }
 
         Ok(txid)
     }
 
-    pub async fn sync(&mut self) -> Result<(), FaucetError> {
+    async fn sync(&mut self) -> Result<(), FaucetError> {
         self.client.sync_and_await().await.map_err(|e| {
             FaucetError::Wallet(format!("Sync failed: {}", e))
         })?;
         Ok(())
     }
 
-    pub fn get_transaction_history(&self, limit: usize) -> Vec<TransactionRecord> {
-        self.history.get_recent(limit)
+    /// Stop any sync zingolib is still running and write the wallet file and
+    /// the faucet history to disk, so a restart picks up a consistent state.
+    async fn persist(&mut self) -> Result<(), FaucetError> {
+        if let Err(e) = self.client.stop_sync() {
+            tracing::debug!("No sync to stop: {}", e);
+        }
+
+        self.save_wallet().await?;
+        self.history.flush()
+    }
+
+    /// Height up to which the wallet has fully scanned the chain.
+    async fn synced_height(&self) -> Option<u64> {
+        self.client
+            .wallet
+            .read()
+            .await
+            .sync_state
+            .fully_scanned_height()
+            .map(|height| u64::from(u32::from(height)))
     }
 
-    pub fn get_stats(&self) -> (usize, f64) {
-        let txs = self.history.get_all();
-        let count = txs.len();
-        let total_sent: f64 = txs.iter().map(|tx| tx.amount).sum();
-        (count, total_sent)
+    fn history(&self) -> &TransactionHistory {
+        &self.history
     }
 }
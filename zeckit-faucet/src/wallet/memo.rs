@@ -0,0 +1,132 @@
+use crate::error::FaucetError;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::Deserialize;
+use std::str::FromStr;
+use zcash_primitives::memo::{Memo, MemoBytes};
+
+/// Memo field accepted by `/request` and `/send`.
+///
+/// A bare JSON string is still accepted and treated as a text memo, so
+/// existing clients keep working. The structured forms are:
+/// `{"text": "..."}`, `{"hex": "..."}`, `{"base64": "..."}` and `{"empty": true}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MemoInput {
+    Plain(String),
+    Structured(MemoSpec),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoSpec {
+    Text(String),
+    Hex(String),
+    Base64(String),
+    Empty(bool),
+}
+
+impl MemoInput {
+    /// Encode the memo as the 512-byte ZIP-302 memo field.
+    ///
+    /// Raw bytes are padded by `MemoBytes` and must still decode as a valid
+    /// `Memo`, so a memo that no wallet could parse is rejected up front.
+    pub fn to_memo_bytes(&self) -> Result<MemoBytes, FaucetError> {
+        let memo_bytes = match self {
+            MemoInput::Plain(text) | MemoInput::Structured(MemoSpec::Text(text)) => {
+                let memo = Memo::from_str(text)
+                    .map_err(|e| FaucetError::Validation(format!("Invalid text memo: {}", e)))?;
+                MemoBytes::from(&memo)
+            }
+            MemoInput::Structured(MemoSpec::Hex(encoded)) => {
+                let bytes = hex::decode(encoded.trim())
+                    .map_err(|e| FaucetError::Validation(format!("Invalid hex memo: {}", e)))?;
+                bytes_to_memo(&bytes)?
+            }
+            MemoInput::Structured(MemoSpec::Base64(encoded)) => {
+                let bytes = BASE64.decode(encoded.trim())
+                    .map_err(|e| FaucetError::Validation(format!("Invalid base64 memo: {}", e)))?;
+                bytes_to_memo(&bytes)?
+            }
+            MemoInput::Structured(MemoSpec::Empty(true)) => MemoBytes::empty(),
+            MemoInput::Structured(MemoSpec::Empty(false)) => {
+                return Err(FaucetError::Validation(
+                    "\"empty\" memo must be true; omit the memo field instead".to_string()
+                ));
+            }
+        };
+
+        Memo::try_from(&memo_bytes)
+            .map_err(|e| FaucetError::Validation(format!("Memo is not valid ZIP-302: {}", e)))?;
+
+        Ok(memo_bytes)
+    }
+}
+
+fn bytes_to_memo(bytes: &[u8]) -> Result<MemoBytes, FaucetError> {
+    MemoBytes::from_bytes(bytes)
+        .map_err(|e| FaucetError::Validation(format!("Invalid memo ({} bytes, max 512): {}", bytes.len(), e)))
+}
+
+/// ZIP-302 classification of an encoded memo, as reported in API responses.
+pub fn memo_kind(memo_bytes: &MemoBytes) -> &'static str {
+    match Memo::try_from(memo_bytes) {
+        Ok(Memo::Empty) => "empty",
+        Ok(Memo::Text(_)) => "text",
+        Ok(Memo::Future(_)) => "future",
+        Ok(Memo::Arbitrary(_)) => "arbitrary",
+        Err(_) => "invalid",
+    }
+}
+
+/// Text content of the memo, or an empty string for non-text memos.
+pub fn memo_text(memo_bytes: &MemoBytes) -> String {
+    match Memo::try_from(memo_bytes) {
+        Ok(Memo::Text(text)) => text.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Hex encoding of the full 512-byte memo field exactly as it was sent.
+pub fn memo_hex(memo_bytes: &MemoBytes) -> String {
+    hex::encode(memo_bytes.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> MemoInput {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_plain_string_is_text_memo() {
+        let memo_bytes = parse(r#""hello""#).to_memo_bytes().unwrap();
+        assert_eq!(memo_kind(&memo_bytes), "text");
+        assert_eq!(memo_text(&memo_bytes), "hello");
+    }
+
+    #[test]
+    fn test_empty_memo_is_f6() {
+        let memo_bytes = parse(r#"{"empty": true}"#).to_memo_bytes().unwrap();
+        assert_eq!(memo_bytes.as_slice()[0], 0xF6);
+        assert_eq!(memo_kind(&memo_bytes), "empty");
+    }
+
+    #[test]
+    fn test_hex_and_base64_keep_exact_bytes() {
+        let from_hex = parse(r#"{"hex": "ff0102"}"#).to_memo_bytes().unwrap();
+        let from_base64 = parse(r#"{"base64": "/wEC"}"#).to_memo_bytes().unwrap();
+        assert_eq!(from_hex, from_base64);
+        assert_eq!(memo_kind(&from_hex), "arbitrary");
+        assert!(memo_hex(&from_hex).starts_with("ff0102"));
+        assert_eq!(memo_hex(&from_hex).len(), 1024);
+    }
+
+    #[test]
+    fn test_oversized_memo_rejected() {
+        let too_long = format!(r#"{{"hex": "{}"}}"#, "00".repeat(513));
+        assert!(parse(&too_long).to_memo_bytes().is_err());
+        assert!(parse(r#"{"empty": false}"#).to_memo_bytes().is_err());
+    }
+}
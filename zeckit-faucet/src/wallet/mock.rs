@@ -0,0 +1,308 @@
+use crate::error::FaucetError;
+use crate::wallet::accounts::{AccountRecord, AccountRegistry};
+use crate::wallet::addresses::{AddressBook, AddressKind, DerivedAddress, Receivers};
+use crate::wallet::ephemeral::EphemeralWallet;
+use crate::wallet::faucet_wallet::FaucetWallet;
+use crate::wallet::history::{TransactionHistory, TransactionKind, TransactionRecord};
+use crate::wallet::manager::Balance;
+use crate::wallet::memo;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tempfile::TempDir;
+use zcash_primitives::memo::MemoBytes;
+use zcash_protocol::value::Zatoshis;
+
+/// Fee the mock charges for a shield, matching what `/shield` reports.
+const SHIELD_FEE: u64 = 10_000;
+
+/// In-memory [`FaucetWallet`] for route tests.
+///
+/// Balances are plain numbers that sends and shields move around; history,
+/// accounts and derived addresses use the real file-backed stores in a
+/// temporary directory. Set `fail_sync` to make `sync` fail.
+pub struct MockWallet {
+    _dir: TempDir,
+    history: TransactionHistory,
+    accounts: AccountRegistry,
+    address_book: AddressBook,
+    balances: HashMap<u32, Balance>,
+    pub received: HashMap<String, u64>,
+    pub seed: Option<String>,
+    pub birthday: u32,
+    pub chain_height: u64,
+    pub synced_height: Option<u64>,
+    pub fail_sync: Option<String>,
+    pub sync_count: u32,
+    next_txid: u64,
+}
+
+impl MockWallet {
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("temp dir for mock wallet");
+
+        Self {
+            history: TransactionHistory::load(dir.path()).unwrap(),
+            accounts: AccountRegistry::load(dir.path()).unwrap(),
+            address_book: AddressBook::load(dir.path()).unwrap(),
+            _dir: dir,
+            balances: HashMap::new(),
+            received: HashMap::new(),
+            seed: Some("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art".to_string()),
+            birthday: 1,
+            chain_height: 100,
+            synced_height: None,
+            fail_sync: None,
+            sync_count: 0,
+            next_txid: 0,
+        }
+    }
+
+    /// Start with the given confirmed balances (in ZEC) on `account`.
+    pub fn fund(mut self, account: u32, transparent_zec: f64, orchard_zec: f64) -> Self {
+        self.set_balance(account, transparent_zec, orchard_zec);
+        self
+    }
+
+    /// Set an account's confirmed transparent and Orchard balances in ZEC.
+    pub fn set_balance(&mut self, account: u32, transparent_zec: f64, orchard_zec: f64) {
+        self.balances.insert(account, Balance {
+            transparent: zatoshis(transparent_zec),
+            sapling: Zatoshis::ZERO,
+            orchard: zatoshis(orchard_zec),
+        });
+    }
+
+    pub fn balance(&self, account: u32) -> Balance {
+        self.balances.get(&account).cloned().unwrap_or(Balance {
+            transparent: Zatoshis::ZERO,
+            sapling: Zatoshis::ZERO,
+            orchard: Zatoshis::ZERO,
+        })
+    }
+
+    fn txid(&mut self) -> String {
+        self.next_txid += 1;
+        format!("{:064x}", self.next_txid)
+    }
+}
+
+impl Default for MockWallet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn zatoshis(zec: f64) -> Zatoshis {
+    Zatoshis::from_u64((zec * 100_000_000.0) as u64).unwrap()
+}
+
+#[async_trait]
+impl FaucetWallet for MockWallet {
+    async fn get_unified_address(&self) -> Result<String, FaucetError> {
+        self.get_account_address(zip32::AccountId::ZERO).await
+    }
+
+    async fn get_transparent_address(&self) -> Result<String, FaucetError> {
+        Ok("tmMockTransparentAddress0".to_string())
+    }
+
+    async fn get_account_address(&self, account: zip32::AccountId) -> Result<String, FaucetError> {
+        let index = u32::from(account);
+        if index >= self.accounts.account_count() {
+            return Err(FaucetError::Wallet(format!("No unified address for account {}", index)));
+        }
+        Ok(format!("uregtest1mockaccount{}", index))
+    }
+
+    async fn new_address(
+        &mut self,
+        receivers: Receivers,
+        account: zip32::AccountId,
+    ) -> Result<Vec<DerivedAddress>, FaucetError> {
+        let mut derived = Vec::new();
+        let n = self.address_book.get_all().len();
+
+        if receivers.has_shielded() {
+            derived.push(DerivedAddress {
+                address: format!("uregtest1mockderived{}", n),
+                kind: AddressKind::Unified,
+                receivers: Receivers { transparent: false, ..receivers }.names(),
+                account: u32::from(account),
+                created_at: chrono::Utc::now(),
+            });
+        }
+        if receivers.sapling && !receivers.orchard {
+            derived.push(DerivedAddress {
+                address: format!("zregtestsapling1mockderived{}", n),
+                kind: AddressKind::Sapling,
+                receivers: vec!["sapling".to_string()],
+                account: u32::from(account),
+                created_at: chrono::Utc::now(),
+            });
+        }
+        if receivers.transparent {
+            derived.push(DerivedAddress {
+                address: format!("tmMockDerived{}", n),
+                kind: AddressKind::Transparent,
+                receivers: vec!["transparent".to_string()],
+                account: u32::from(account),
+                created_at: chrono::Utc::now(),
+            });
+        }
+
+        for address in &derived {
+            self.address_book.add(address.clone())?;
+        }
+        Ok(derived)
+    }
+
+    fn derived_addresses(&self) -> &[DerivedAddress] {
+        self.address_book.get_all()
+    }
+
+    async fn received_by_address(&self) -> HashMap<String, u64> {
+        self.received.clone()
+    }
+
+    async fn derive_ephemeral(&self, birthday: u32) -> Result<EphemeralWallet, FaucetError> {
+        Ok(EphemeralWallet {
+            mnemonic: "ephemeral mock mnemonic".to_string(),
+            birthday,
+            unified_address: "uregtest1mockephemeral".to_string(),
+            sapling_address: "zregtestsapling1mockephemeral".to_string(),
+            transparent_address: "tmMockEphemeral".to_string(),
+        })
+    }
+
+    fn accounts(&self) -> &AccountRegistry {
+        &self.accounts
+    }
+
+    async fn create_account(
+        &mut self,
+        name: &str,
+        api_key: Option<String>,
+    ) -> Result<AccountRecord, FaucetError> {
+        self.accounts.add(name, api_key)
+    }
+
+    async fn viewing_keys(&self, account: zip32::AccountId) -> Result<(String, String), FaucetError> {
+        let index = u32::from(account);
+        Ok((format!("uviewregtest1mock{}", index), format!("uivkregtest1mock{}", index)))
+    }
+
+    async fn birthday(&self) -> u32 {
+        self.birthday
+    }
+
+    fn seed_phrase(&self) -> Option<String> {
+        self.seed.clone()
+    }
+
+    async fn get_account_balance(&self, account: zip32::AccountId) -> Result<Balance, FaucetError> {
+        Ok(self.balance(u32::from(account)))
+    }
+
+    async fn shield_to_orchard(&mut self) -> Result<String, FaucetError> {
+        let balance = self.balance(0);
+        if balance.transparent == Zatoshis::ZERO {
+            return Err(FaucetError::Wallet("No transparent funds to shield".to_string()));
+        }
+
+        let shielded = balance.transparent.into_u64().saturating_sub(SHIELD_FEE);
+        self.balances.insert(0, Balance {
+            transparent: Zatoshis::ZERO,
+            sapling: balance.sapling,
+            orchard: (balance.orchard + Zatoshis::from_u64(shielded).unwrap()).unwrap(),
+        });
+
+        let txid = self.txid();
+        let to_address = self.get_unified_address().await?;
+        self.history.add_transaction(TransactionRecord {
+            kind: TransactionKind::Shield,
+            account: 0,
+            txid: txid.clone(),
+            to_address,
+            amount: balance.transparent_zec(),
+            timestamp: chrono::Utc::now(),
+            memo: String::new(),
+            memo_hex: None,
+        })?;
+
+        Ok(txid)
+    }
+
+    async fn send_payments(
+        &mut self,
+        payments: Vec<(String, f64, Option<MemoBytes>)>,
+        account: zip32::AccountId,
+    ) -> Result<String, FaucetError> {
+        let index = u32::from(account);
+        let total_zec: f64 = payments.iter().map(|(_, amount, _)| amount).sum();
+
+        let balance = self.balance(index);
+        let orchard = balance.orchard.into_u64()
+            .checked_sub(zatoshis(total_zec).into_u64())
+            .ok_or_else(|| FaucetError::InsufficientBalance(format!(
+                "Need {} ZEC, have {} ZEC in Orchard pool",
+                total_zec,
+                balance.orchard_zec()
+            )))?;
+        self.balances.insert(index, Balance {
+            orchard: Zatoshis::from_u64(orchard).unwrap(),
+            ..balance
+        });
+
+        let txid = self.txid();
+        for (to_address, amount_zec, memo_bytes) in payments {
+            self.history.add_transaction(TransactionRecord {
+                kind: TransactionKind::Send,
+                account: index,
+                txid: txid.clone(),
+                to_address,
+                amount: amount_zec,
+                timestamp: chrono::Utc::now(),
+                memo: memo_bytes.as_ref().map(memo::memo_text).unwrap_or_default(),
+                memo_hex: memo_bytes.as_ref().map(memo::memo_hex),
+            })?;
+        }
+
+        Ok(txid)
+    }
+
+    async fn sync(&mut self) -> Result<(), FaucetError> {
+        if let Some(error) = &self.fail_sync {
+            return Err(FaucetError::Wallet(format!("Sync failed: {}", error)));
+        }
+        self.sync_count += 1;
+        self.synced_height = Some(self.chain_height);
+        Ok(())
+    }
+
+    async fn synced_height(&self) -> Option<u64> {
+        self.synced_height
+    }
+
+    async fn rebuild_from_seed(&mut self, _from_height: u32, clear_history: bool) -> Result<(), FaucetError> {
+        self.synced_height = None;
+        if clear_history {
+            self.history.clear()?;
+            self.address_book.clear()?;
+        }
+        Ok(())
+    }
+
+    fn set_server_uri(&mut self, uri: &str) -> Result<(), FaucetError> {
+        uri.parse::<axum::http::Uri>()
+            .map(|_| ())
+            .map_err(|e| FaucetError::Validation(format!("Invalid indexer URI {}: {}", uri, e)))
+    }
+
+    async fn persist(&mut self) -> Result<(), FaucetError> {
+        self.history.flush()
+    }
+
+    fn history(&self) -> &TransactionHistory {
+        &self.history
+    }
+}
@@ -1,5 +1,17 @@
 pub mod manager;
 pub mod history;
 pub mod seed;
+pub mod memo;
+pub mod auto_shield;
+pub mod refill;
+pub mod sync;
+pub mod accounts;
+pub mod addresses;
+pub mod ephemeral;
+pub mod backend;
+pub mod faucet_wallet;
+#[cfg(test)]
+pub mod mock;
 
+pub use faucet_wallet::{FaucetWallet, SharedWallet};
 pub use manager::WalletManager;
@@ -0,0 +1,180 @@
+use crate::config::Config;
+use crate::error::FaucetError;
+use crate::shutdown::ShutdownSignal;
+use crate::wallet::sync::{tracked_sync, SyncStatus};
+use crate::wallet::{FaucetWallet, SharedWallet};
+use crate::zebra::ZebraClient;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::Duration;
+use tracing::info;
+use zcash_protocol::value::Zatoshis;
+
+/// Regtest coinbase only becomes spendable after this many confirmations.
+const COINBASE_MATURITY: u32 = 100;
+
+/// How long each sync may take; the first one scans every freshly mined block.
+const SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Where the refill watchdog currently is, as reported by `/health`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RefillPhase {
+    Disabled,
+    Idle,
+    Mining,
+    Syncing,
+    Shielding,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefillStatus {
+    pub phase: RefillPhase,
+    pub threshold: f64,
+    pub refill_count: u64,
+    pub last_refill: Option<DateTime<Utc>>,
+    pub last_shield_txid: Option<String>,
+    pub last_error: Option<String>,
+}
+
+impl RefillStatus {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            phase: if config.refill_enabled { RefillPhase::Idle } else { RefillPhase::Disabled },
+            threshold: config.refill_threshold,
+            refill_count: 0,
+            last_refill: None,
+            last_shield_txid: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Settings for the low-balance watchdog.
+///
+/// This is regtest, so when the Orchard balance runs low the faucet mints
+/// its own funds: Zebra mines enough blocks to the faucet's transparent
+/// address for fresh coinbase to mature, and the result is shielded.
+#[derive(Debug, Clone)]
+pub struct RefillPolicy {
+    pub threshold: Zatoshis,
+    pub blocks: u32,
+    pub interval: Duration,
+}
+
+impl RefillPolicy {
+    /// Build the policy from config, or `None` when refill mode is off.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.refill_enabled {
+            return None;
+        }
+
+        Some(Self {
+            threshold: Zatoshis::from_u64((config.refill_threshold * 100_000_000.0) as u64)
+                .unwrap_or(Zatoshis::ZERO),
+            blocks: config.refill_blocks.max(COINBASE_MATURITY + 1),
+            interval: Duration::from_secs(config.refill_interval_secs.max(1)),
+        })
+    }
+}
+
+pub async fn run(
+    wallet: SharedWallet,
+    zebra: ZebraClient,
+    status: Arc<RwLock<RefillStatus>>,
+    sync_status: Arc<RwLock<SyncStatus>>,
+    policy: RefillPolicy,
+    mut shutdown: ShutdownSignal,
+) {
+    info!(
+        "⛏ Starting refill watchdog (threshold {} ZEC Orchard, {} blocks per refill)",
+        policy.threshold.into_u64() as f64 / 100_000_000.0,
+        policy.blocks
+    );
+
+    let mut interval = tokio::time::interval(policy.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
+
+        let orchard = match wallet.read().await.get_balance().await {
+            Ok(balance) => balance.orchard,
+            Err(e) => {
+                tracing::debug!("Refill check skipped - balance check failed: {}", e);
+                continue;
+            }
+        };
+
+        if orchard >= policy.threshold {
+            continue;
+        }
+
+        info!(
+            "⛏ Orchard balance {} ZEC is below refill threshold, minting funds",
+            orchard.into_u64() as f64 / 100_000_000.0
+        );
+
+        match refill(&wallet, &zebra, &status, &sync_status, &policy).await {
+            Ok(txid) => {
+                let mut status = status.write().await;
+                status.phase = RefillPhase::Idle;
+                status.refill_count += 1;
+                status.last_refill = Some(Utc::now());
+                status.last_shield_txid = txid;
+                status.last_error = None;
+                info!("⛏ Refill #{} complete", status.refill_count);
+            }
+            Err(e) => {
+                tracing::warn!("⚠ Refill failed: {} (will retry)", e);
+                let mut status = status.write().await;
+                status.phase = RefillPhase::Idle;
+                status.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+async fn set_phase(status: &RwLock<RefillStatus>, phase: RefillPhase) {
+    status.write().await.phase = phase;
+}
+
+/// Mine, sync, shield, then mine one more block so the shield confirms.
+/// Returns the shield txid, or `None` if the auto-shield task got there first.
+/// The syncs show up in `/sync/status` like the background ones.
+async fn refill(
+    wallet: &RwLock<dyn FaucetWallet>,
+    zebra: &ZebraClient,
+    status: &RwLock<RefillStatus>,
+    sync_status: &RwLock<SyncStatus>,
+    policy: &RefillPolicy,
+) -> Result<Option<String>, FaucetError> {
+    set_phase(status, RefillPhase::Mining).await;
+    zebra.generate(policy.blocks).await?;
+
+    set_phase(status, RefillPhase::Syncing).await;
+    tracked_sync(&mut *wallet.write().await, sync_status, SYNC_TIMEOUT).await?;
+
+    set_phase(status, RefillPhase::Shielding).await;
+    let txid = {
+        let mut wallet = wallet.write().await;
+        if wallet.get_balance().await?.transparent == Zatoshis::ZERO {
+            None
+        } else {
+            Some(wallet.shield_to_orchard().await?)
+        }
+    };
+
+    set_phase(status, RefillPhase::Mining).await;
+    zebra.generate(1).await?;
+
+    set_phase(status, RefillPhase::Syncing).await;
+    tracked_sync(&mut *wallet.write().await, sync_status, SYNC_TIMEOUT).await?;
+
+    Ok(txid)
+}
@@ -1,10 +1,14 @@
 use crate::error::FaucetError;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
+/// Public regtest mnemonic used when no seed is configured. Its first
+/// transparent address is the `miner_address` shipped in zebra.toml.
+const DEFAULT_SEED: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+
 pub struct SeedManager {
-    seed_file: std::path::PathBuf,
+    seed_file: PathBuf,
 }
 
 impl SeedManager {
@@ -13,33 +17,77 @@ impl SeedManager {
             seed_file: data_dir.join(".wallet_seed"),
         }
     }
-    
-    /// Get or create deterministic seed for this ZecKit installation
+
+    /// Seed persisted by a previous run, if any.
+    pub fn stored_seed(&self) -> Option<String> {
+        fs::read_to_string(&self.seed_file)
+            .ok()
+            .map(|seed| seed.trim().to_string())
+            .filter(|seed| !seed.is_empty())
+    }
+
+    /// Get the seed for this ZecKit installation, persisting it for future runs.
+    ///
+    /// Precedence: the file named by `FAUCET_SEED_FILE`, then `FAUCET_SEED`
+    /// (a mnemonic, or `random` to generate one on first start), then the
+    /// stored seed, then the public default regtest seed. This matches
+    /// `zeckit up`, where `--seed-file` beats `ZECKIT_FAUCET_SEED`.
     pub fn get_or_create_seed(&self) -> Result<String, FaucetError> {
-        // If seed file exists, use it
-        if self.seed_file.exists() {
+        let stored = self.stored_seed();
+
+        let seed_phrase = if let Some(path) = env_non_empty("FAUCET_SEED_FILE") {
+            info!("Using wallet seed from {}", path);
+            let seed = fs::read_to_string(&path)
+                .map_err(|e| FaucetError::Wallet(format!("Failed to read seed file {}: {}", path, e)))?;
+            normalize(&seed)
+        } else if let Some(seed) = env_non_empty("FAUCET_SEED") {
+            if seed.trim() == "random" {
+                // Generate once; later restarts keep the stored random seed
+                match &stored {
+                    Some(stored) => {
+                        info!("Loading existing wallet seed from {:?}", self.seed_file);
+                        stored.clone()
+                    }
+                    None => {
+                        info!("Generating new random wallet seed");
+                        generate_mnemonic()
+                    }
+                }
+            } else {
+                info!("Using wallet seed from FAUCET_SEED");
+                normalize(&seed)
+            }
+        } else if let Some(stored) = stored.clone() {
             info!("Loading existing wallet seed from {:?}", self.seed_file);
-            let seed = fs::read_to_string(&self.seed_file)
-                .map_err(|e| FaucetError::Wallet(format!("Failed to read seed file: {}", e)))?;
-            return Ok(seed.trim().to_string());
+            stored
+        } else {
+            info!("⚠️  Using default regtest seed - same wallet for all ZecKit installations");
+            info!("   Set FAUCET_SEED (or `zeckit up --seed`) to use your own");
+            DEFAULT_SEED.to_string()
+        };
+
+        bip0039::Mnemonic::<bip0039::English>::from_phrase(seed_phrase.as_str())
+            .map_err(|e| FaucetError::Wallet(format!("Invalid mnemonic phrase: {}", e)))?;
+
+        if stored.as_deref() != Some(seed_phrase.as_str()) {
+            fs::write(&self.seed_file, &seed_phrase)
+                .map_err(|e| FaucetError::Wallet(format!("Failed to write seed file: {}", e)))?;
+            info!("Seed saved to {:?}", self.seed_file);
         }
-        
-        // Generate new seed for this installation
-        info!("Generating new deterministic seed for this ZecKit installation");
-        
-        // Use a default regtest seed (same for all installations)
-        // This ensures everyone gets the same wallet addresses for testing
-        let seed_phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
-        
-        info!("⚠️  Using default regtest seed - same wallet for all ZecKit installations");
-        info!("   This is intentional for regtest development environments");
-        
-        // Save seed for future runs
-        fs::write(&self.seed_file, seed_phrase)
-            .map_err(|e| FaucetError::Wallet(format!("Failed to write seed file: {}", e)))?;
-        
-        info!("Seed saved to {:?}", self.seed_file);
-        
-        Ok(seed_phrase.to_string())
+
+        Ok(seed_phrase)
     }
-}
\ No newline at end of file
+}
+
+fn env_non_empty(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn normalize(phrase: &str) -> String {
+    phrase.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(crate) fn generate_mnemonic() -> String {
+    let mnemonic: bip0039::Mnemonic = bip0039::Mnemonic::generate(bip0039::Count::Words24);
+    mnemonic.phrase().to_string()
+}
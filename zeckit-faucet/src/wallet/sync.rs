@@ -0,0 +1,108 @@
+use crate::error::FaucetError;
+use crate::wallet::FaucetWallet;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::time::Duration;
+
+/// Outcome of the most recent wallet syncs, shared by the background loop
+/// and the `/sync` endpoints.
+///
+/// Lives outside the wallet lock so `/sync/status` can answer while a sync
+/// holds the wallet for writing.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncStatus {
+    pub running: bool,
+    pub sync_count: u64,
+    pub wallet_height: Option<u64>,
+    pub last_sync: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Confirmed Orchard balance of the default account after the last
+    /// successful sync, so readiness checks need no wallet lock.
+    pub orchard_zatoshis: Option<u64>,
+    /// The most recently requested rescan.
+    pub rescan: Option<Rescan>,
+}
+
+/// A rebuild-and-rescan requested through the admin API. Clients wait for
+/// their `id` to finish rather than for any sync, since the background loop
+/// keeps syncing in between.
+#[derive(Debug, Clone, Serialize)]
+pub struct Rescan {
+    pub id: u64,
+    pub from_height: u32,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+/// Note that the wallet was just rebuilt to rescan from `from_height`,
+/// superseding any earlier rescan. Returns the new rescan's id.
+pub async fn start_rescan(status: &RwLock<SyncStatus>, from_height: u32) -> u64 {
+    let mut status = status.write().await;
+    let id = status.rescan.as_ref().map_or(1, |rescan| rescan.id + 1);
+    status.wallet_height = None;
+    status.orchard_zatoshis = None;
+    status.rescan = Some(Rescan { id, from_height, finished_at: None, error: None });
+    id
+}
+
+/// Run the sync that carries out rescan `id` and record how it ended.
+pub async fn finish_rescan(
+    wallet: &mut dyn FaucetWallet,
+    status: &RwLock<SyncStatus>,
+    id: u64,
+    timeout: Duration,
+) -> Result<(), FaucetError> {
+    let result = tracked_sync(wallet, status, timeout).await;
+
+    if let Some(rescan) = status.write().await.rescan.as_mut().filter(|rescan| rescan.id == id) {
+        rescan.finished_at = Some(Utc::now());
+        rescan.error = result.as_ref().err().map(|e| e.to_string());
+    }
+
+    result
+}
+
+/// Sync an already-locked wallet and record the outcome in `status`.
+pub async fn tracked_sync(
+    wallet: &mut dyn FaucetWallet,
+    status: &RwLock<SyncStatus>,
+    timeout: Duration,
+) -> Result<(), FaucetError> {
+    status.write().await.running = true;
+
+    let result = match tokio::time::timeout(timeout, wallet.sync()).await {
+        Ok(result) => result,
+        Err(_) => Err(FaucetError::Wallet(format!(
+            "Sync timed out after {}s",
+            timeout.as_secs()
+        ))),
+    };
+
+    let wallet_height = wallet.synced_height().await;
+    let orchard = match &result {
+        Ok(()) => wallet.get_balance().await.ok().map(|b| b.orchard.into_u64()),
+        Err(_) => None,
+    };
+
+    let mut status = status.write().await;
+    status.running = false;
+    if wallet_height.is_some() {
+        status.wallet_height = wallet_height;
+    }
+    match &result {
+        Ok(()) => {
+            status.sync_count += 1;
+            status.last_sync = Some(Utc::now());
+            status.last_error = None;
+            if orchard.is_some() {
+                status.orchard_zatoshis = orchard;
+            }
+        }
+        Err(e) => {
+            status.last_error = Some(e.to_string());
+        }
+    }
+
+    result
+}
@@ -0,0 +1,77 @@
+use crate::error::FaucetError;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Minimal JSON-RPC client for the Zebra node the faucet runs against.
+#[derive(Debug, Clone)]
+pub struct ZebraClient {
+    client: Client,
+    url: String,
+}
+
+impl ZebraClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.to_string(),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value, timeout: Duration) -> Result<Value, FaucetError> {
+        let response = self.client
+            .post(&self.url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": method,
+                "method": method,
+                "params": params
+            }))
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| FaucetError::Rpc(format!("{} request failed: {}", method, e)))?;
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| FaucetError::Rpc(format!("Failed to parse {} response: {}", method, e)))?;
+
+        if let Some(error) = body.get("error").filter(|e| !e.is_null()) {
+            return Err(FaucetError::Rpc(format!("{} returned error: {}", method, error)));
+        }
+
+        body.get("result")
+            .cloned()
+            .ok_or_else(|| FaucetError::Rpc(format!("No result in {} response", method)))
+    }
+
+    /// Current chain tip height.
+    pub async fn get_block_count(&self) -> Result<u64, FaucetError> {
+        self.call("getblockcount", json!([]), Duration::from_secs(5))
+            .await?
+            .as_u64()
+            .ok_or_else(|| FaucetError::Rpc("Invalid getblockcount response".to_string()))
+    }
+
+    /// Mine `blocks` regtest blocks to Zebra's configured miner address.
+    /// Returns the hashes of the new blocks.
+    pub async fn generate(&self, blocks: u32) -> Result<Vec<String>, FaucetError> {
+        let result = self.call("generate", json!([blocks]), Duration::from_secs(30 + blocks as u64)).await?;
+
+        Ok(result
+            .as_array()
+            .map(|hashes| {
+                hashes.iter()
+                    .filter_map(|h| h.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Confirmations of `txid`; 0 while it is still in the mempool.
+    pub async fn get_confirmations(&self, txid: &str) -> Result<u64, FaucetError> {
+        let result = self.call("getrawtransaction", json!([txid, 1]), Duration::from_secs(5)).await?;
+        Ok(result["confirmations"].as_u64().unwrap_or(0))
+    }
+}